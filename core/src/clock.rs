@@ -0,0 +1,33 @@
+use chrono::{DateTime, Duration, Utc};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use std::time::Duration as StdDuration;
+
+// The offset between Kraken's clock and the local one, updated by the clients from the server time they receive.
+// Clones share the offset, so one clock can also drive a `MonotonicNonce` or stamp client-side events.
+#[derive(Debug, Clone, Default)]
+pub struct ServerClock {
+    // Server time minus local time, in milliseconds
+    offset: Arc<AtomicI64>,
+}
+
+impl ServerClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn offset(&self) -> Duration {
+        Duration::milliseconds(self.offset.load(Ordering::SeqCst))
+    }
+
+    // The local time corrected by the last observed offset
+    pub fn now(&self) -> DateTime<Utc> {
+        Utc::now() + self.offset()
+    }
+
+    // Call right after receiving `server`. It is assumed to be stamped halfway through the round trip.
+    pub fn observe(&self, server: DateTime<Utc>, round_trip: StdDuration) {
+        let local = Utc::now() - Duration::from_std(round_trip / 2).unwrap_or_else(|_| Duration::zero());
+        self.offset.store((server - local).num_milliseconds(), Ordering::SeqCst);
+    }
+}
@@ -0,0 +1,11 @@
+mod clock;
+mod middleware;
+mod nonce;
+mod paginate;
+mod transport;
+
+pub use clock::ServerClock;
+pub use middleware::Middleware;
+pub use nonce::{Millis, MonotonicNonce, Nanos, NonceSource, NonceUnit, PersistentNonce};
+pub use paginate::{paginate, Paginated};
+pub use transport::{HttpRequest, HttpResponse, Transport};
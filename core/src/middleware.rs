@@ -0,0 +1,11 @@
+use crate::transport::{HttpRequest, HttpResponse};
+use std::time::Duration;
+
+// Observes every request sent by the clients, e.g. for audit logs or metrics. `endpoint` is
+// `Request::ENDPOINT`, `req` has the credential headers redacted and `E` is the client's error type.
+pub trait Middleware<E>: Send + Sync {
+    fn on_request(&self, _endpoint: &str, _req: &HttpRequest) {}
+
+    // `resp` is the raw response before it is parsed, so Kraken errors still arrive as `Ok`
+    fn on_response(&self, _endpoint: &str, _req: &HttpRequest, _resp: Result<&HttpResponse, &E>, _latency: Duration) {}
+}
@@ -0,0 +1,154 @@
+use crate::ServerClock;
+use chrono::{DateTime, Utc};
+use log::warn;
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+pub trait NonceSource: Send + Sync {
+    // Every call must return a value greater than the previous one
+    fn next_nonce(&self) -> u64;
+}
+
+impl<N: NonceSource + ?Sized> NonceSource for Arc<N> {
+    fn next_nonce(&self) -> u64 {
+        (**self).next_nonce()
+    }
+}
+
+// The resolution of the nonces, Kraken Futures counts in nanoseconds and spot in milliseconds
+pub trait NonceUnit: fmt::Debug + Default + Send + Sync {
+    const PER_SECOND: u64;
+
+    fn since_epoch(time: DateTime<Utc>) -> u64;
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Millis;
+
+impl NonceUnit for Millis {
+    const PER_SECOND: u64 = 1_000;
+
+    fn since_epoch(time: DateTime<Utc>) -> u64 {
+        time.timestamp_millis() as u64
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Nanos;
+
+impl NonceUnit for Nanos {
+    const PER_SECOND: u64 = 1_000_000_000;
+
+    fn since_epoch(time: DateTime<Utc>) -> u64 {
+        time.timestamp_nanos() as u64
+    }
+}
+
+// Time since the epoch in `U`, bumped by one whenever the clock hasn't moved or went backwards.
+#[derive(Debug, Default)]
+pub struct MonotonicNonce<U: NonceUnit> {
+    last: AtomicU64,
+    clock: Option<ServerClock>,
+    unit: PhantomData<U>,
+}
+
+impl<U: NonceUnit> MonotonicNonce<U> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Never returns a nonce lower than or equal to `last`
+    pub fn starting_after(last: u64) -> Self {
+        MonotonicNonce {
+            last: AtomicU64::new(last),
+            clock: None,
+            unit: PhantomData,
+        }
+    }
+
+    // Follows the server's clock instead of the local one
+    pub fn with_clock(self, clock: ServerClock) -> Self {
+        MonotonicNonce { clock: Some(clock), ..self }
+    }
+}
+
+impl<U: NonceUnit> NonceSource for MonotonicNonce<U> {
+    fn next_nonce(&self) -> u64 {
+        let now = match &self.clock {
+            Some(clock) => clock.now(),
+            None => Utc::now(),
+        };
+        let now = U::since_epoch(now);
+        let prev = self.last.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |last| Some(now.max(last + 1))).unwrap();
+        now.max(prev + 1)
+    }
+}
+
+// A `MonotonicNonce` that survives restarts by storing a high-water mark in a file. The mark is
+// written ahead of the issued nonces, so the file is only touched once every `RESERVATION_SECS`.
+#[derive(Debug)]
+pub struct PersistentNonce<U: NonceUnit> {
+    inner: MonotonicNonce<U>,
+    path: PathBuf,
+    reserved: Mutex<u64>,
+}
+
+const RESERVATION_SECS: u64 = 60;
+
+impl<U: NonceUnit> PersistentNonce<U> {
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let reserved = match fs::read_to_string(&path) {
+            Ok(s) => s.trim().parse().map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => 0,
+            Err(e) => return Err(e),
+        };
+
+        Ok(PersistentNonce {
+            inner: MonotonicNonce::starting_after(reserved),
+            path,
+            reserved: Mutex::new(reserved),
+        })
+    }
+
+    // Follows the server's clock instead of the local one
+    pub fn with_clock(self, clock: ServerClock) -> Self {
+        PersistentNonce {
+            inner: self.inner.with_clock(clock),
+            ..self
+        }
+    }
+}
+
+impl<U: NonceUnit> NonceSource for PersistentNonce<U> {
+    fn next_nonce(&self) -> u64 {
+        let mut reserved = self.reserved.lock().unwrap();
+        let nonce = self.inner.next_nonce();
+
+        if nonce >= *reserved {
+            *reserved = nonce + RESERVATION_SECS * U::PER_SECOND;
+            if let Err(e) = write_atomically(&self.path, &reserved.to_string()) {
+                warn!("Cannot persist nonce to {}: {}", self.path.display(), e);
+            }
+        }
+
+        nonce
+    }
+}
+
+// Writes a synced temporary file next to `path` and renames it over `path`, so a crash leaves either
+// the old or the new mark, never a truncated one
+fn write_atomically(path: &Path, contents: &str) -> io::Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+
+    let mut file = fs::File::create(&tmp)?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()?;
+    fs::rename(&tmp, path)
+}
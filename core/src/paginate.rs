@@ -0,0 +1,37 @@
+use futures::future::Future;
+use futures::stream::{iter, unfold, Stream, TryStreamExt};
+
+// Requests whose responses are split into pages, e.g. continuation tokens or offsets.
+pub trait Paginated: Clone {
+    // The response to one request
+    type Page;
+    type Item;
+
+    // The request for the page after `page`, or None if `page` is the last page.
+    fn next_page(&self, page: &Self::Page) -> Option<Self>;
+
+    fn into_items(page: Self::Page) -> Vec<Self::Item>;
+}
+
+// Fetches every page of `req` in turn and yields their items. Ends after the first error.
+pub fn paginate<R, E, F, Fut>(req: R, fetch: F) -> impl Stream<Item = Result<R::Item, E>>
+where
+    R: Paginated,
+    F: Fn(R) -> Fut,
+    Fut: Future<Output = Result<R::Page, E>>,
+{
+    unfold(Some(req), move |req| {
+        let page = req.map(|req| (fetch(req.clone()), req));
+        async move {
+            let (page, req) = page?;
+            match page.await {
+                Ok(page) => {
+                    let next = req.next_page(&page);
+                    Some((Ok(iter(R::into_items(page).into_iter().map(Ok))), next))
+                }
+                Err(e) => Some((Err(e), None)),
+            }
+        }
+    })
+    .try_flatten()
+}
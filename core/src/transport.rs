@@ -0,0 +1,71 @@
+use futures::future::{BoxFuture, FutureExt};
+use http::{Method, StatusCode};
+use reqwest::Client;
+use url::Url;
+
+// A fully prepared request: signed, with the query string already in the url for GET and PUT
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+    pub method: Method,
+    pub url: Url,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl HttpRequest {
+    // A copy that is safe to log, with the values of `credential_headers` replaced
+    pub fn redacted(&self, credential_headers: &[&str]) -> HttpRequest {
+        let headers = self
+            .headers
+            .iter()
+            .map(|(name, value)| {
+                if credential_headers.contains(&name.as_str()) {
+                    (name.clone(), "<redacted>".to_string())
+                } else {
+                    (name.clone(), value.clone())
+                }
+            })
+            .collect();
+
+        HttpRequest {
+            method: self.method.clone(),
+            url: self.url.clone(),
+            headers,
+            body: self.body.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: StatusCode,
+    pub body: String,
+}
+
+// Sends requests for the clients, `E` being the client's error type. `reqwest::Client` is the default,
+// pass a configured one to control timeouts, proxies or TLS, or implement this to answer requests
+// without touching the network.
+pub trait Transport<E>: Send + Sync {
+    fn send(&self, req: HttpRequest) -> BoxFuture<'static, Result<HttpResponse, E>>;
+}
+
+impl<E> Transport<E> for Client
+where
+    E: From<reqwest::Error> + Send + 'static,
+{
+    fn send(&self, req: HttpRequest) -> BoxFuture<'static, Result<HttpResponse, E>> {
+        let mut builder = self.request(req.method, req.url);
+        for (name, value) in &req.headers {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+        let resp = builder.body(req.body).send();
+
+        async move {
+            let resp = resp.await?;
+            let status = resp.status();
+            let body = resp.text().await?;
+            Ok(HttpResponse { status, body })
+        }
+        .boxed()
+    }
+}
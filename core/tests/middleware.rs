@@ -0,0 +1,48 @@
+use http::{Method, StatusCode};
+use kraken_core::{HttpRequest, HttpResponse, Middleware};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use url::Url;
+
+#[derive(Debug)]
+struct TestError;
+
+// Only overrides `on_response`, `on_request` keeps the default
+#[derive(Clone, Default)]
+struct Statuses {
+    seen: Arc<Mutex<Vec<Option<u16>>>>,
+}
+
+impl Middleware<TestError> for Statuses {
+    fn on_response(&self, _endpoint: &str, _req: &HttpRequest, resp: Result<&HttpResponse, &TestError>, _latency: Duration) {
+        self.seen.lock().unwrap().push(resp.ok().map(|r| r.status.as_u16()));
+    }
+}
+
+struct Silent;
+
+impl Middleware<TestError> for Silent {}
+
+#[test]
+fn test_default_methods_do_nothing() {
+    let statuses = Statuses::default();
+    let middlewares: Vec<Arc<dyn Middleware<TestError>>> = vec![Arc::new(Silent), Arc::new(statuses.clone())];
+    let req = HttpRequest {
+        method: Method::GET,
+        url: Url::parse("https://futures.kraken.com/derivatives/api/v3/tickers").unwrap(),
+        headers: vec![],
+        body: String::new(),
+    };
+    let resp = HttpResponse {
+        status: StatusCode::OK,
+        body: "{}".to_string(),
+    };
+
+    for middleware in &middlewares {
+        middleware.on_request("/tickers", &req);
+        middleware.on_response("/tickers", &req, Ok(&resp), Duration::from_millis(1));
+        middleware.on_response("/tickers", &req, Err(&TestError), Duration::from_millis(1));
+    }
+
+    assert_eq!(*statuses.seen.lock().unwrap(), vec![Some(200), None]);
+}
@@ -0,0 +1,90 @@
+use kraken_core::{Millis, MonotonicNonce, Nanos, NonceSource, NonceUnit, PersistentNonce};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::thread;
+
+fn unique_across_threads<U: NonceUnit + 'static>() {
+    let nonce = Arc::new(MonotonicNonce::<U>::new());
+
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            let nonce = nonce.clone();
+            thread::spawn(move || {
+                let mut issued = vec![];
+                for _ in 0..1000 {
+                    issued.push(nonce.next_nonce());
+                }
+                issued
+            })
+        })
+        .collect();
+
+    let mut all = HashSet::new();
+    for handle in handles {
+        let issued = handle.join().unwrap();
+        assert!(issued.windows(2).all(|w| w[0] < w[1]));
+        all.extend(issued);
+    }
+    assert_eq!(all.len(), 8000);
+}
+
+#[test]
+fn test_monotonic_nonce_is_unique_across_threads() {
+    unique_across_threads::<Millis>();
+    unique_across_threads::<Nanos>();
+}
+
+#[test]
+fn test_monotonic_nonce_ignores_clock_going_backwards() {
+    let future = u64::MAX / 2;
+    let nonce = MonotonicNonce::<Millis>::starting_after(future);
+
+    assert_eq!(nonce.next_nonce(), future + 1);
+    assert_eq!(nonce.next_nonce(), future + 2);
+}
+
+#[test]
+fn test_monotonic_nonce_units() {
+    let millis = MonotonicNonce::<Millis>::new().next_nonce();
+    let nanos = MonotonicNonce::<Nanos>::new().next_nonce();
+
+    // Both taken within a second of each other
+    assert!((nanos / 1_000_000).abs_diff(millis) < 1_000);
+}
+
+fn survives_restart<U: NonceUnit>(name: &str) {
+    let path = std::env::temp_dir().join(format!("kraken-core-nonce-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let last = {
+        let nonce = PersistentNonce::<U>::open(&path).unwrap();
+        nonce.next_nonce();
+        nonce.next_nonce()
+    };
+
+    let nonce = PersistentNonce::<U>::open(&path).unwrap();
+    assert!(nonce.next_nonce() > last);
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_persistent_nonce_survives_restart() {
+    survives_restart::<Millis>("millis");
+    survives_restart::<Nanos>("nanos");
+}
+
+#[test]
+fn test_persistent_nonce_replaces_the_file() {
+    let path = std::env::temp_dir().join(format!("kraken-core-nonce-atomic-{}", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let nonce = PersistentNonce::<Millis>::open(&path).unwrap().next_nonce();
+
+    let reserved: u64 = std::fs::read_to_string(&path).unwrap().parse().unwrap();
+    assert!(reserved > nonce);
+    // The temporary file was renamed over the mark
+    assert!(!path.with_file_name(format!("kraken-core-nonce-atomic-{}.tmp", std::process::id())).exists());
+
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,59 @@
+use futures::executor::block_on;
+use futures::future::ready;
+use futures::stream::{StreamExt, TryStreamExt};
+use kraken_core::{paginate, Paginated};
+use std::cell::RefCell;
+
+// Pages of two items at `offset`, out of `total`
+#[derive(Clone, Debug, PartialEq)]
+struct Numbers {
+    offset: u32,
+    total: u32,
+}
+
+impl Paginated for Numbers {
+    type Page = Vec<u32>;
+    type Item = u32;
+
+    fn next_page(&self, page: &Self::Page) -> Option<Self> {
+        let offset = page.last()? + 1;
+        if offset < self.total {
+            Some(Self { offset, ..self.clone() })
+        } else {
+            None
+        }
+    }
+
+    fn into_items(page: Self::Page) -> Vec<Self::Item> {
+        page
+    }
+}
+
+fn page(req: &Numbers) -> Vec<u32> {
+    (req.offset..req.total.min(req.offset + 2)).collect()
+}
+
+#[test]
+fn test_paginate_follows_next_page() {
+    let sent = RefCell::new(vec![]);
+    let stream = paginate(Numbers { offset: 0, total: 5 }, |req| {
+        sent.borrow_mut().push(req.offset);
+        ready(Ok::<_, ()>(page(&req)))
+    });
+
+    assert_eq!(block_on(stream.try_collect::<Vec<_>>()), Ok(vec![0, 1, 2, 3, 4]));
+    assert_eq!(*sent.borrow(), vec![0, 2, 4]);
+}
+
+#[test]
+fn test_paginate_ends_after_an_error() {
+    let sent = RefCell::new(vec![]);
+    let stream = paginate(Numbers { offset: 0, total: 10 }, |req| {
+        sent.borrow_mut().push(req.offset);
+        ready(if req.offset == 2 { Err("failed") } else { Ok(page(&req)) })
+    });
+
+    let items: Vec<_> = block_on(stream.collect());
+    assert_eq!(items, vec![Ok(0), Ok(1), Err("failed")]);
+    assert_eq!(*sent.borrow(), vec![0, 2]);
+}
@@ -0,0 +1,32 @@
+use http::Method;
+use kraken_core::HttpRequest;
+use url::Url;
+
+#[test]
+fn test_redacted_replaces_only_credential_headers() {
+    let req = HttpRequest {
+        method: Method::POST,
+        url: Url::parse("https://api.kraken.com/0/private/Balance").unwrap(),
+        headers: vec![
+            ("API-Key".to_string(), "key".to_string()),
+            ("API-Sign".to_string(), "signature".to_string()),
+            ("Content-Type".to_string(), "application/x-www-form-urlencoded".to_string()),
+        ],
+        body: "nonce=1".to_string(),
+    };
+
+    let redacted = req.redacted(&["API-Key", "API-Sign"]);
+
+    assert_eq!(
+        redacted.headers,
+        vec![
+            ("API-Key".to_string(), "<redacted>".to_string()),
+            ("API-Sign".to_string(), "<redacted>".to_string()),
+            ("Content-Type".to_string(), "application/x-www-form-urlencoded".to_string()),
+        ]
+    );
+    assert_eq!(redacted.method, req.method);
+    assert_eq!(redacted.url, req.url);
+    assert_eq!(redacted.body, req.body);
+    assert_eq!(req.headers[1].1, "signature");
+}
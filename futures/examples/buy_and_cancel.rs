@@ -1,8 +1,8 @@
 #![allow(unused_imports)]
 
+use anyhow::Error;
 use dotenv::dotenv;
 use env_logger::init;
-use failure::Error;
 use fehler::throws;
 use futures::{SinkExt, StreamExt};
 use kraken_futures::rest::{CancelAllOrdersRequest, CancelOrderRequest, KrakenRest, SendOrderRequest};
@@ -30,10 +30,10 @@ async fn main() -> Result<(), Error> {
 
     let client = KrakenRest::with_credential(None, &opt.kraken_api_key, &opt.kraken_api_secret);
 
-    let resp = client.request(SendOrderRequest::limit(Symbol::PerpetualInverse("XBTUSD".parse()?), 30000., -1)).await?;
+    let resp = client.request(SendOrderRequest::limit(Symbol::PerpetualInverse("XBTUSD".parse()?), 30000., -1)?).await?;
     println!("{:?}", resp);
 
-    let resp = client.request(SendOrderRequest::limit(Symbol::PerpetualInverse("XBTUSD".parse()?), 30000., -1)).await?;
+    let resp = client.request(SendOrderRequest::limit(Symbol::PerpetualInverse("XBTUSD".parse()?), 30000., -1)?).await?;
     println!("{:?}", resp);
 
     let resp = client.request(CancelAllOrdersRequest::all()).await?;
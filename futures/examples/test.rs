@@ -1,8 +1,8 @@
 #![allow(unused_imports)]
 
+use anyhow::Error;
 use dotenv::dotenv;
 use env_logger::init;
-use failure::Error;
 use fehler::throws;
 use futures::{SinkExt, StreamExt};
 use kraken_futures::rest::{KrakenRest, TickersRequest};
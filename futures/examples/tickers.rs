@@ -1,6 +1,6 @@
+use anyhow::Error;
 use dotenv::dotenv;
 use env_logger::init;
-use failure::Error;
 use kraken_futures::rest::{KrakenRest, TickersRequest};
 
 #[tokio::main]
@@ -0,0 +1,40 @@
+use serde::{de::Error as DeError, Deserialize, Deserializer, Serializer};
+
+// Kraken's history service encodes decimals as strings, while the rest of the API uses plain numbers.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StringOrF64 {
+    String(String),
+    F64(f64),
+}
+
+pub fn string_or_f64<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match StringOrF64::deserialize(deserializer)? {
+        StringOrF64::String(s) => s.parse().map_err(DeError::custom),
+        StringOrF64::F64(f) => Ok(f),
+    }
+}
+
+pub fn option_string_or_f64<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<StringOrF64>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(StringOrF64::String(s)) if s.is_empty() => Ok(None),
+        Some(StringOrF64::String(s)) => s.parse().map(Some).map_err(DeError::custom),
+        Some(StringOrF64::F64(f)) => Ok(Some(f)),
+    }
+}
+
+// Account names and units are lowercase on the transfer endpoints, e.g. fi_xbtusd and xbt.
+pub fn lowercase<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: ToString,
+    S: Serializer,
+{
+    serializer.serialize_str(&value.to_string().to_lowercase())
+}
@@ -1,16 +1,21 @@
 pub mod constants;
+pub(crate) mod de;
 mod fill_type;
 mod order;
+mod self_trade_strategy;
 mod send_order_status;
 mod side;
 mod symbol;
+mod trailing_stop_deviation_unit;
 mod trigger_signal;
 
 pub use fill_type::FillType;
 pub use order::{Order, OrderEvent, OrderType};
+pub use self_trade_strategy::SelfTradeStrategy;
 pub use send_order_status::SendOrderStatus;
 pub use side::{PositionSide, Side};
 pub use symbol::{Currency, Pair, Symbol};
+pub use trailing_stop_deviation_unit::TrailingStopDeviationUnit;
 pub use trigger_signal::TriggerSignal;
 
 use serde::Deserialize;
@@ -26,8 +26,9 @@ impl PartialEq for Order {
     }
 }
 
+// Told apart by Kraken's `type` field
 #[derive(Debug, Deserialize, Clone)]
-#[serde(untagged)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum OrderEvent {
     #[serde(rename_all = "camelCase")]
     Execution {
@@ -37,18 +38,41 @@ pub enum OrderEvent {
         order_prior_edit: Option<Order>,
         order_prior_execution: Order,
     },
+    #[serde(rename = "PLACE", rename_all = "camelCase")]
     NewOrder {
         order: Order,
-        reason: Option<String>,
-        r#type: String,
+        reduced_quantity: Option<f64>,
     },
+    #[serde(rename_all = "camelCase")]
+    Edit {
+        old: Order,
+        new: Order,
+        reduced_quantity: Option<f64>,
+    },
+    // An order cancelled by the request, or as its side effect, e.g. a resting order hit by self trade prevention
+    Cancel {
+        uid: Uuid,
+        order: Order,
+    },
+    // The order was not placed, e.g. with reason POST_WOULD_EXECUTE
+    Reject {
+        uid: Uuid,
+        order: Order,
+        reason: String,
+    },
+    // Event types Kraken added after this list was written
+    #[serde(other)]
+    Other,
 }
 
 impl OrderEvent {
-    pub fn order_id(&self) -> Uuid {
+    // None for event types this client doesn't know
+    pub fn order_id(&self) -> Option<Uuid> {
         match self {
-            OrderEvent::Execution { order_prior_execution, .. } => order_prior_execution.order_id,
-            OrderEvent::NewOrder { order, .. } => order.order_id,
+            OrderEvent::Execution { order_prior_execution, .. } => Some(order_prior_execution.order_id),
+            OrderEvent::NewOrder { order, .. } | OrderEvent::Cancel { order, .. } | OrderEvent::Reject { order, .. } => Some(order.order_id),
+            OrderEvent::Edit { new, .. } => Some(new.order_id),
+            OrderEvent::Other => None,
         }
     }
 }
@@ -70,4 +94,10 @@ pub enum OrderType {
     // ioc for an immediate-or-cancel order
     #[serde(rename = "ioc")]
     IOC,
+    // mkt for a market order
+    #[serde(rename = "mkt")]
+    Mkt,
+    // trailing_stop for a trailing stop order
+    #[serde(rename = "trailing_stop")]
+    TrailingStop,
 }
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SelfTradeStrategy {
+    // The incoming order is rejected with selfFill, this is Kraken's default
+    RejectTaker,
+    // The resting order is cancelled if it belongs to the same account
+    CancelMakerSelf,
+    // Both the resting and the incoming order are cancelled
+    CancelMakerTaker,
+}
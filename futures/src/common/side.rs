@@ -1,9 +1,11 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Debug, Clone, Copy)]
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub enum Side {
+    #[serde(alias = "Buy")]
     Buy,
+    #[serde(alias = "Sell")]
     Sell,
 }
 
@@ -51,6 +51,8 @@ pub enum Symbol {
     FutureVanilla(Pair, Option<u64>),
     PerpetualInverse(Pair),
     PerpetualVanilla(Pair),
+    // Multi-collateral perpetuals
+    PerpetualFlexible(Pair),
     Index(Pair),
     ReferenceRate(Pair),
 }
@@ -63,6 +65,7 @@ impl Symbol {
             Symbol::FutureVanilla(p, _) => Some(*p),
             Symbol::PerpetualInverse(p) => Some(*p),
             Symbol::PerpetualVanilla(p) => Some(*p),
+            Symbol::PerpetualFlexible(p) => Some(*p),
             Symbol::Index(p) => Some(*p),
             Symbol::ReferenceRate(p) => Some(*p),
         }
@@ -70,8 +73,8 @@ impl Symbol {
 }
 
 impl FromStr for Symbol {
-    type Err = failure::Error;
-    #[throws(failure::Error)]
+    type Err = KrakenError;
+    #[throws(KrakenError)]
     fn from_str(s: &str) -> Symbol {
         from_str(&format!("\"{}\"", s))?
     }
@@ -87,6 +90,7 @@ impl std::fmt::Display for Symbol {
             Symbol::FutureVanilla(p, Some(e)) => write!(f, "FV_{}_{}", p, e),
             Symbol::PerpetualInverse(p) => write!(f, "PI_{}", p),
             Symbol::PerpetualVanilla(p) => write!(f, "PV_{}", p),
+            Symbol::PerpetualFlexible(p) => write!(f, "PF_{}", p),
             Symbol::Index(p) => write!(f, "IN_{}", p),
             Symbol::ReferenceRate(p) => write!(f, "RR_{}", p),
         }
@@ -129,6 +133,7 @@ impl<'de> Deserialize<'de> for Symbol {
             ["FV", pair] => Symbol::FutureVanilla(pair.parse().map_err(DeError::custom)?, None),
             ["PI", pair] => Symbol::PerpetualInverse(pair.parse().map_err(DeError::custom)?),
             ["PV", pair] => Symbol::PerpetualVanilla(pair.parse().map_err(DeError::custom)?),
+            ["PF", pair] => Symbol::PerpetualFlexible(pair.parse().map_err(DeError::custom)?),
             ["IN", pair] => Symbol::Index(pair.parse().map_err(DeError::custom)?),
             ["RR", pair] => Symbol::ReferenceRate(pair.parse().map_err(DeError::custom)?),
             _ => throw!(DeError::custom(KrakenError::ParseSymbolFailed(s))),
@@ -152,8 +157,8 @@ impl std::fmt::Debug for Pair {
 }
 
 impl FromStr for Pair {
-    type Err = failure::Error;
-    #[throws(failure::Error)]
+    type Err = KrakenError;
+    #[throws(KrakenError)]
     fn from_str(s: &str) -> Pair {
         from_str(&format!("\"{}\"", s))?
     }
@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TrailingStopDeviationUnit {
+    // The max deviation is a percentage of the trigger price
+    Percent,
+    // The max deviation is an absolute amount in the quote currency
+    QuoteCurrency,
+}
@@ -1,21 +1,100 @@
-use failure::Fail;
+use crate::rest::RatePool;
+use http::StatusCode;
+use std::time::Duration;
+use thiserror::Error;
 
-#[derive(Fail, Debug)]
+#[derive(Error, Debug)]
 pub enum KrakenError {
-    #[fail(display = "No Api key set for private api")]
+    #[error("No Api key set for private api")]
     NoApiKeySet,
-    #[fail(display = "Kraken error {:?}", _0)]
-    KrakenError(String),
-    #[fail(display = "Cannot deserialize response {}", _0)]
-    CannotDeserializeResponse(String),
-    #[fail(display = "Websocket closed")]
+    #[error("Kraken error {error:?} ({status}): {message}")]
+    Api { error: ApiError, message: String, status: StatusCode },
+    #[error("Rate limit of {pool:?} exhausted, retry after {retry_after:?}")]
+    RateLimited { pool: RatePool, retry_after: Duration },
+    #[error("Transport error {0}")]
+    Transport(#[from] reqwest::Error),
+    #[error("Websocket error {0}")]
+    Websocket(#[from] tungstenite::Error),
+    #[error("Cannot deserialize response ({status}) {body}")]
+    CannotDeserializeResponse {
+        status: StatusCode,
+        body: String,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("Json error {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Cannot encode request {0}")]
+    UrlEncode(#[from] serde_urlencoded::ser::Error),
+    #[error("Invalid url {0}")]
+    Url(#[from] url::ParseError),
+    #[error("Api secret is not valid base64 {0}")]
+    InvalidApiSecret(#[from] base64::DecodeError),
+    #[error("Websocket closed")]
     WebsocketClosed,
-    #[fail(display = "Unexpected websocket binary content {:?}", _0)]
+    #[error("Unexpected websocket binary content {0:?}")]
     UnexpectedWebsocketBinaryContent(Vec<u8>),
-    #[fail(display = "Failed to parse pair {}", _0)]
+    #[error("Failed to parse pair {0}")]
     ParsePairFailed(String),
-    #[fail(display = "Failed to parse symbol {}", _0)]
+    #[error("Failed to parse symbol {0}")]
     ParseSymbolFailed(String),
-    #[fail(display = "Failed to parse currency {}", _0)]
+    #[error("Failed to parse currency {0}")]
     ParseCurrencyFailed(String),
+    #[error("Invalid order: {0}")]
+    InvalidOrder(String),
+}
+
+// The error codes documented by Kraken Futures. Some errors carry details after a colon, e.g. "invalidArgument: size".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApiError {
+    ApiLimitExceeded,
+    AuthenticationError,
+    AccountInactive,
+    NonceBelowThreshold,
+    NonceDuplicate,
+    InvalidArgument,
+    InvalidUnit,
+    InsufficientFunds,
+    MarketUnavailable,
+    RequiredArgumentMissing,
+    Unavailable,
+    ServerError,
+    Unknown,
+}
+
+impl ApiError {
+    // Whether sending the same request again may succeed
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ApiError::ApiLimitExceeded | ApiError::NonceBelowThreshold | ApiError::NonceDuplicate | ApiError::Unavailable | ApiError::ServerError => true,
+            ApiError::AuthenticationError
+            | ApiError::AccountInactive
+            | ApiError::InvalidArgument
+            | ApiError::InvalidUnit
+            | ApiError::InsufficientFunds
+            | ApiError::MarketUnavailable
+            | ApiError::RequiredArgumentMissing
+            | ApiError::Unknown => false,
+        }
+    }
+}
+
+impl From<&str> for ApiError {
+    fn from(error: &str) -> ApiError {
+        match error.split(':').next().unwrap_or_default().trim() {
+            "apiLimitExceeded" => ApiError::ApiLimitExceeded,
+            "authenticationError" => ApiError::AuthenticationError,
+            "accountInactive" => ApiError::AccountInactive,
+            "nonceBelowThreshold" => ApiError::NonceBelowThreshold,
+            "nonceDuplicate" => ApiError::NonceDuplicate,
+            "invalidArgument" => ApiError::InvalidArgument,
+            "invalidUnit" => ApiError::InvalidUnit,
+            "insufficientFunds" | "insufficientAvailableFunds" => ApiError::InsufficientFunds,
+            "marketUnavailable" => ApiError::MarketUnavailable,
+            "requiredArgumentMissing" => ApiError::RequiredArgumentMissing,
+            "unavailable" => ApiError::Unavailable,
+            "Server Error" | "serverError" => ApiError::ServerError,
+            _ => ApiError::Unknown,
+        }
+    }
 }
@@ -1,10 +1,16 @@
-use super::models::Request;
-use crate::errors::KrakenError;
+use super::models::{Request, DERIVATIVES_PATH};
+use super::nonce::{MonotonicNonce, NonceSource};
+use super::rate_limiter::RateLimiter;
+use super::retry::{in_flight, Idempotency, RetryPolicy};
+use crate::errors::{ApiError, KrakenError};
 use base64::{decode as b64decode, encode as b64encode};
 use chrono::{DateTime, Utc};
 use fehler::{throw, throws};
+use futures::stream::Stream;
 use http::Method;
-use reqwest::{Client, Response};
+use kraken_core::ServerClock;
+use kraken_core::{paginate, HttpRequest, HttpResponse, Middleware, Paginated, Transport};
+use reqwest::Client;
 use ring::digest::{digest, SHA256};
 use ring::hmac;
 use serde::de::DeserializeOwned;
@@ -12,66 +18,192 @@ use serde::{Deserialize, Serialize};
 use serde_json::{from_str, to_string as to_jstring, to_value};
 use serde_urlencoded::to_string as to_ustring;
 use std::str;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::time::delay_for;
 use url::Url;
 
-const REST_URL: &'static str = "https://futures.kraken.com/derivatives/api/v3";
+const REST_URL: &str = "https://futures.kraken.com";
+// Redacted from the requests handed to middlewares
+const CREDENTIAL_HEADERS: [&str; 2] = ["APIKey", "Authent"];
+
+// Requests add their own base path, so a url ending in the v3 path as taken by earlier versions is cut
+// back to the host instead of doubling the path.
+fn host_url(url: Option<&str>) -> String {
+    let url = url.unwrap_or(REST_URL).trim_end_matches('/');
+    url.strip_suffix(DERIVATIVES_PATH).unwrap_or(url).to_string()
+}
 
 #[derive(Clone)]
 pub struct KrakenRest {
     url: String,
-    client: Client,
+    transport: Arc<dyn Transport<KrakenError>>,
     credential: Option<(String, String)>,
+    rate_limiter: Option<RateLimiter>,
+    retry_policy: RetryPolicy,
+    nonce: Arc<dyn NonceSource>,
+    middlewares: Vec<Arc<dyn Middleware<KrakenError>>>,
+    clock: ServerClock,
 }
 
 impl KrakenRest {
+    // `url` is the host only, e.g. "https://demo-futures.kraken.com", None for the production host
     pub fn new<'a, T>(url: T) -> Self
     where
         T: Into<Option<&'a str>>,
     {
         KrakenRest {
-            url: url.into().unwrap_or(REST_URL).into(),
-            client: Client::new(),
+            url: host_url(url.into()),
+            transport: Arc::new(Client::new()),
             credential: None,
+            rate_limiter: None,
+            retry_policy: RetryPolicy::default(),
+            nonce: Arc::new(MonotonicNonce::new()),
+            middlewares: vec![],
+            clock: ServerClock::new(),
         }
     }
 
+    // Takes the same `url` as `new`
     pub fn with_credential<'a, T>(url: T, api_key: &str, api_secret: &str) -> Self
     where
         T: Into<Option<&'a str>>,
     {
         KrakenRest {
-            url: url.into().unwrap_or(REST_URL).into(),
-            client: Client::new(),
+            url: host_url(url.into()),
+            transport: Arc::new(Client::new()),
             credential: Some((api_key.into(), api_secret.into())),
+            rate_limiter: None,
+            retry_policy: RetryPolicy::default(),
+            nonce: Arc::new(MonotonicNonce::new()),
+            middlewares: vec![],
+            clock: ServerClock::new(),
+        }
+    }
+
+    // Budgets requests on the client side before they reach Kraken. Clients cloned afterwards share the limiter.
+    pub fn with_rate_limiter(self, rate_limiter: RateLimiter) -> Self {
+        KrakenRest {
+            rate_limiter: Some(rate_limiter),
+            ..self
+        }
+    }
+
+    // The policy used by `request_with_retry`
+    pub fn with_retry_policy(self, retry_policy: RetryPolicy) -> Self {
+        KrakenRest { retry_policy, ..self }
+    }
+
+    // Clones share the nonce source. Pass an `Arc` to share one between separately created clients.
+    pub fn with_nonce_source<N: NonceSource + 'static>(self, nonce: N) -> Self {
+        KrakenRest { nonce: Arc::new(nonce), ..self }
+    }
+
+    // Sends requests through `transport` instead of a default `reqwest::Client`
+    pub fn with_transport<T: Transport<KrakenError> + 'static>(self, transport: T) -> Self {
+        KrakenRest {
+            transport: Arc::new(transport),
+            ..self
         }
     }
 
-    pub async fn request_with_retry_nonce<R>(&self, req: R) -> failure::Fallible<R::Response>
+    // Tracks the offset to Kraken's clock in `clock`. Pass a clone of it to `MonotonicNonce::with_clock`
+    // to sign with server time.
+    pub fn with_server_clock(self, clock: ServerClock) -> Self {
+        KrakenRest { clock, ..self }
+    }
+
+    // The offset observed from the last response
+    pub fn server_clock(&self) -> &ServerClock {
+        &self.clock
+    }
+
+    // Middlewares are called in the order they were added
+    pub fn with_middleware<M: Middleware<KrakenError> + 'static>(mut self, middleware: M) -> Self {
+        self.middlewares.push(Arc::new(middleware));
+        self
+    }
+
+    #[deprecated(note = "nonces from a shared NonceSource cannot collide, use request_with_retry")]
+    pub async fn request_with_retry_nonce<R>(&self, req: R) -> Result<R::Response, KrakenError>
+    where
+        R: Request + Clone,
+        R::Response: DeserializeOwned,
+    {
+        let policy = RetryPolicy {
+            max_attempts: u32::MAX,
+            initial_backoff: Duration::from_secs(0),
+            jitter: false,
+            retryable: |e| {
+                matches!(
+                    e,
+                    KrakenError::Api {
+                        error: ApiError::NonceDuplicate,
+                        ..
+                    }
+                )
+            },
+            ..RetryPolicy::default()
+        };
+        self.retry(req, &policy).await
+    }
+
+    // Sends `req` again on retryable errors, as long as resending it cannot execute it twice
+    pub async fn request_with_retry<R>(&self, req: R) -> Result<R::Response, KrakenError>
     where
         R: Request + Clone,
         R::Response: DeserializeOwned,
     {
+        self.retry(req, &self.retry_policy).await
+    }
+
+    async fn retry<R>(&self, req: R, policy: &RetryPolicy) -> Result<R::Response, KrakenError>
+    where
+        R: Request + Clone,
+        R::Response: DeserializeOwned,
+    {
+        let mut attempt = 1;
         loop {
-            match self.request(req.clone()).await {
+            let e = match self.request(req.clone()).await {
                 Ok(o) => return Ok(o),
-                Err(e) => match e.downcast_ref::<KrakenError>() {
-                    Some(KrakenError::KrakenError(content)) if content.starts_with("nonceDuplicate:") => continue,
-                    _ => throw!(e),
-                },
+                Err(e) => e,
             };
+
+            if attempt >= policy.max_attempts || !(policy.retryable)(&e) || !self.safe_to_resend(&req, &e).await {
+                return Err(e);
+            }
+
+            delay_for(policy.backoff(attempt, &e)).await;
+            attempt += 1;
         }
     }
 
-    #[throws(failure::Error)]
+    async fn safe_to_resend<R: Request>(&self, req: &R, error: &KrakenError) -> bool {
+        // Kraken answered with an error, or the request was never sent
+        if !in_flight(error) {
+            return true;
+        }
+
+        match req.idempotency() {
+            Idempotency::Idempotent => true,
+            Idempotency::Unsafe => false,
+        }
+    }
+
+    #[throws(KrakenError)]
     pub async fn request<R>(&self, req: R) -> R::Response
     where
         R: Request,
         R::Response: DeserializeOwned,
     {
-        let url = format!("{}{}", &*REST_URL, R::ENDPOINT);
-        let url = Url::parse(&url)?;
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire(R::RATE_POOL, req.cost()).await?;
+        }
 
-        let nonce = Utc::now().timestamp_nanos();
+        let url = format!("{}{}{}", self.url, R::BASE_PATH, R::ENDPOINT);
+        let mut url = Url::parse(&url)?;
+
+        let nonce = self.nonce.next_nonce();
 
         let mut query = vec![];
 
@@ -79,28 +211,68 @@ impl KrakenRest {
             query = req.to_url_query();
             query.push(("nonce".to_string(), nonce.to_string()));
         }
+        let data = to_ustring(&query)?;
+
+        let mut headers = vec![
+            ("User-Agent".to_string(), "kraken-rs".to_string()),
+            ("Content-Type".to_string(), "application/x-www-form-urlencoded".to_string()),
+        ];
+
+        if R::SIGNED {
+            let (key, signature) = self.signature(&url, &data, nonce)?;
+            headers.push(("APIKey".to_string(), key.to_string()));
+            headers.push(("Authent".to_string(), signature));
+            headers.push(("Nonce".to_string(), nonce.to_string()));
+        }
 
-        let mut req = match R::METHOD {
-            Method::GET => self.client.request(R::METHOD, url.clone()).query(&query),
-            Method::POST => self.client.request(R::METHOD, url.clone()).form(&query),
+        let body = match R::METHOD {
+            Method::GET | Method::PUT => {
+                if !data.is_empty() {
+                    url.set_query(Some(&data));
+                }
+                String::new()
+            }
+            Method::POST => data,
             _ => unreachable!("Unsupported HTTP method"),
         };
 
-        if R::SIGNED {
-            let (key, signature) = self.signature(&url, &to_ustring(&query)?, nonce)?;
-            req = req.header("APIKey", key).header("Authent", signature).header("Nonce", nonce);
+        let req = HttpRequest {
+            method: R::METHOD,
+            url,
+            headers,
+            body,
+        };
+
+        let logged = req.redacted(&CREDENTIAL_HEADERS);
+        for middleware in &self.middlewares {
+            middleware.on_request(R::ENDPOINT, &logged);
         }
 
-        let resp = req
-            .header("User-Agent", "kraken-rs")
-            .header("Content-Type", "application/x-www-form-urlencoded")
-            .send()
-            .await?;
+        let started = Instant::now();
+        let resp = self.transport.send(req).await;
+        let latency = started.elapsed();
+        for middleware in &self.middlewares {
+            middleware.on_response(R::ENDPOINT, &logged, resp.as_ref(), latency);
+        }
 
-        self.handle_response(resp).await?
+        self.handle_response(resp?, latency)?
     }
 
-    #[throws(failure::Error)]
+    // Requests every page of `req` in turn, following the continuation returned by each response.
+    pub fn stream<R>(&self, req: R) -> impl Stream<Item = Result<R::Item, KrakenError>>
+    where
+        R: Request + Paginated<Page = <R as Request>::Response>,
+        R::Response: DeserializeOwned,
+    {
+        let client = self.clone();
+
+        paginate(req, move |req| {
+            let client = client.clone();
+            async move { client.request(req).await }
+        })
+    }
+
+    #[throws(KrakenError)]
     fn check_key(&self) -> (&str, &str) {
         match self.credential.as_ref() {
             None => throw!(KrakenError::NoApiKeySet),
@@ -108,8 +280,8 @@ impl KrakenRest {
         }
     }
 
-    #[throws(failure::Error)]
-    pub(crate) fn signature(&self, url: &Url, body: &str, nonce: i64) -> (&str, String) {
+    #[throws(KrakenError)]
+    pub(crate) fn signature(&self, url: &Url, body: &str, nonce: u64) -> (&str, String) {
         let (key, secret) = self.check_key()?;
 
         // Concatenate postData + nonce + endpointPath
@@ -130,25 +302,32 @@ impl KrakenRest {
         (key, signature)
     }
 
-    async fn handle_response<T: DeserializeOwned>(&self, resp: Response) -> Result<T, failure::Error> {
-        let resp = resp.text().await?;
+    #[throws(KrakenError)]
+    fn handle_response<T: DeserializeOwned>(&self, resp: HttpResponse, latency: Duration) -> T {
+        let HttpResponse { status, body } = resp;
+
+        // Checked first, as an error body can also match a response whose fields are all optional
+        if let Ok(e) = from_str::<KrakenRestErrorResponse>(&body) {
+            throw!(KrakenError::Api {
+                error: ApiError::from(e.error.as_str()),
+                message: e.error,
+                status,
+            });
+        }
 
-        if let Ok(p) = from_str::<KrakenRestResponse<T>>(&resp) {
-            return Ok(p.payload);
-        } else if let Ok(e) = from_str::<KrakenRestErrorResponse>(&resp) {
-            throw!(KrakenError::from(e))
-        } else {
-            throw!(KrakenError::CannotDeserializeResponse(resp))
+        match from_str::<KrakenRestResponse<T>>(&body) {
+            Ok(p) => {
+                if let Some(server_time) = p.server_time {
+                    self.clock.observe(server_time, latency);
+                }
+                p.payload
+            }
+            Err(source) => throw!(KrakenError::CannotDeserializeResponse { status, body, source }),
         }
     }
 }
 
 trait ToUrlQuery: Serialize {
-    fn to_url_query_string(&self) -> String {
-        let vec = self.to_url_query();
-        vec.into_iter().map(|(k, v)| format!("{}={}", k, v)).collect::<Vec<_>>().join("&")
-    }
-
     fn to_url_query(&self) -> Vec<(String, String)> {
         let v = to_value(self).unwrap();
         let v = v.as_object().unwrap();
@@ -172,17 +351,10 @@ trait ToUrlQuery: Serialize {
 
 impl<S: Serialize> ToUrlQuery for S {}
 
-impl From<KrakenRestErrorResponse> for KrakenError {
-    fn from(error: KrakenRestErrorResponse) -> KrakenError {
-        KrakenError::KrakenError(error.error)
-    }
-}
-
 #[derive(Deserialize, Debug, Clone)]
 pub(crate) struct KrakenRestResponse<T> {
-    result: Success,
     #[serde(rename = "serverTime")]
-    pub(crate) server_time: DateTime<Utc>,
+    pub(crate) server_time: Option<DateTime<Utc>>,
     #[serde(flatten)]
     pub(crate) payload: T,
 }
@@ -190,18 +362,24 @@ pub(crate) struct KrakenRestResponse<T> {
 // The error response from bitmex;
 #[derive(Deserialize, Debug, Clone)]
 pub(crate) struct KrakenRestErrorResponse {
-    result: Error,
     pub(crate) error: String,
 }
 
-#[derive(Deserialize, Debug, Clone)]
-enum Error {
-    #[serde(rename = "error")]
-    Error,
-}
-
-#[derive(Deserialize, Debug, Clone)]
-enum Success {
-    #[serde(rename = "success")]
-    Success,
+#[cfg(test)]
+mod tests {
+    use super::KrakenRest;
+    use url::Url;
+
+    // Kraken publishes no complete futures example, so this reuses the spot example secret and nonce.
+    // The expected value was computed independently from https://docs.futures.kraken.com/#http-api-http-api-introduction-generate-authentication-strings
+    #[test]
+    fn test_signature() {
+        let client = KrakenRest::with_credential(None, "key", "kQH5HW/8p1uGOVjbgWA7FunAmGO8lsSUXNsu3eow76sz84Q18fWxnyRzBHCd3pd5nE9qa99HAZtuZuj6F1huXg==");
+        let url = Url::parse("https://futures.kraken.com/derivatives/api/v3/sendorder").unwrap();
+        let body = "limitPrice=9400&orderType=lmt&side=buy&size=1&symbol=PI_XBTUSD";
+
+        let (key, signature) = client.signature(&url, body, 1616492376594).unwrap();
+        assert_eq!(key, "key");
+        assert_eq!(signature, "aK4W7cyIdOJl8Olzohj05nOpTzxgQ0UXHy8pPcBSsZa5aeBSrVlKi2lPtal9k1KDhMKIXOEOqcXN5i+F9CEcRw==");
+    }
 }
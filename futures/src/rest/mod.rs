@@ -1,11 +1,30 @@
 mod client;
 mod models;
+mod nonce;
+mod rate_limiter;
+mod retry;
 
 pub use client::KrakenRest;
+pub use kraken_core::ServerClock;
+pub use kraken_core::{HttpRequest, HttpResponse, Middleware, Paginated, Transport};
 pub use models::Request;
 pub use models::{AccountDetail, AccountsRequest, AccountsResponse, Auxiliary, MarginRequirements};
+pub use models::{AccountLogEntry, AccountLogRequest, AccountLogResponse, EventsRequest, HistoryEvent, Sort};
 pub use models::{CancelAllOrdersRequest, CancelAllOrdersResponse, CancelledOrder};
 pub use models::{CancelOrderRequest, CancelOrderResponse, CancelStatus, Status};
+pub use models::{ExecutionEventsRequest, ExecutionHistoryEvent, HistoryExecution, HistoryOrder, HistoryTriggerOrder};
+pub use models::{FeeSchedule, FeeScheduleVolumesRequest, FeeScheduleVolumesResponse, FeeSchedulesRequest, FeeSchedulesResponse, FeeTier};
+pub use models::{HistoryElement, HistoryPage, OrderEventsRequest, OrderHistoryEvent, TriggerEventsRequest, TriggerHistoryEvent};
+pub use models::{Instrument, InstrumentsRequest, InstrumentsResponse};
+pub use models::{LeveragePreference, LeveragePreferencesRequest, LeveragePreferencesResponse};
+pub use models::{Notification, NotificationPriority, NotificationType, NotificationsRequest, NotificationsResponse};
+pub use models::{OpenOrder, OpenOrdersRequest, OpenOrdersResponse};
 pub use models::{OpenPosition, OpenPositionsRequest, OpenPositionsResponse};
 pub use models::{OrderbookRequest, OrderbookResponse, TickersRequest, TickersResponse};
+pub use models::{PnlPreference, PnlPreferencesRequest, PnlPreferencesResponse, SetPnlPreferenceRequest, SetPnlPreferenceResponse};
 pub use models::{SendOrderRequest, SendOrderResponse, SendStatus};
+pub use models::{SetLeveragePreferenceRequest, SetLeveragePreferenceResponse};
+pub use models::{TransferRequest, TransferResponse, WithdrawalRequest, WithdrawalResponse};
+pub use nonce::{MonotonicNonce, NonceSource, PersistentNonce};
+pub use rate_limiter::{RateLimitMode, RateLimiter, RatePool};
+pub use retry::{Idempotency, RetryPolicy};
@@ -48,5 +48,6 @@ impl Request for AccountsRequest {
     const SIGNED: bool = true;
     const ENDPOINT: &'static str = "/accounts";
     const HAS_PAYLOAD: bool = false;
+    const COST: u32 = 2;
     type Response = AccountsResponse;
 }
@@ -1,5 +1,6 @@
 use super::Request;
 use crate::common::{constants, Either, OrderEvent, Symbol};
+use crate::rest::Idempotency;
 use chrono::{DateTime, Utc};
 use http::Method;
 use serde::{Deserialize, Serialize};
@@ -54,5 +55,11 @@ impl Request for CancelAllOrdersRequest {
     const SIGNED: bool = true;
     const ENDPOINT: &'static str = "/cancelallorders";
     const HAS_PAYLOAD: bool = true;
+    const COST: u32 = 25;
     type Response = CancelAllOrdersResponse;
+
+    // Cancelling twice only reports the order as not found
+    fn idempotency(&self) -> Idempotency {
+        Idempotency::Idempotent
+    }
 }
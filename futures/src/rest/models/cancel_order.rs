@@ -1,5 +1,6 @@
 use super::Request;
 use crate::common::OrderEvent;
+use crate::rest::Idempotency;
 use http::Method;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -53,5 +54,11 @@ impl Request for CancelOrderRequest {
     const SIGNED: bool = true;
     const ENDPOINT: &'static str = "/cancelorder";
     const HAS_PAYLOAD: bool = true;
+    const COST: u32 = 10;
     type Response = CancelOrderResponse;
+
+    // Cancelling twice only reports the order as not found
+    fn idempotency(&self) -> Idempotency {
+        Idempotency::Idempotent
+    }
 }
@@ -0,0 +1,72 @@
+use super::{InstrumentsResponse, Request};
+use crate::common::Symbol;
+use http::Method;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Serialize, Debug, Clone)]
+pub struct FeeSchedulesRequest;
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeSchedulesResponse {
+    pub fee_schedules: Vec<FeeSchedule>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct FeeSchedule {
+    pub uid: String,
+    pub name: String,
+    pub tiers: Vec<FeeTier>,
+}
+
+// Fees are in percent. A negative maker fee is a rebate.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeTier {
+    pub maker_fee: f64,
+    pub taker_fee: f64,
+    pub usd_volume: f64,
+}
+
+impl FeeSchedulesResponse {
+    // Resolves the tier that applies to `symbol` given our trailing volume per fee schedule. Volume below
+    // the first threshold gets the lowest tier, which is the base tier.
+    pub fn tier_for(&self, symbol: Symbol, instruments: &InstrumentsResponse, volumes: &FeeScheduleVolumesResponse) -> Option<FeeTier> {
+        let symbol = symbol.to_string();
+        let uid = instruments.instruments.iter().find(|i| i.symbol.eq_ignore_ascii_case(&symbol))?.fee_schedule_uid.as_ref()?;
+        let schedule = self.fee_schedules.iter().find(|s| &s.uid == uid)?;
+        let volume = volumes.volumes_by_fee_schedule.get(uid).copied().unwrap_or(0.);
+
+        let tiers = schedule.tiers.iter();
+        let reached = tiers.clone().filter(|t| t.usd_volume <= volume).max_by(|a, b| a.usd_volume.total_cmp(&b.usd_volume));
+        reached.or_else(|| tiers.min_by(|a, b| a.usd_volume.total_cmp(&b.usd_volume))).copied()
+    }
+}
+
+impl Request for FeeSchedulesRequest {
+    const METHOD: Method = Method::GET;
+    const SIGNED: bool = false;
+    const ENDPOINT: &'static str = "/feeschedules";
+    const HAS_PAYLOAD: bool = false;
+    type Response = FeeSchedulesResponse;
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct FeeScheduleVolumesRequest;
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeScheduleVolumesResponse {
+    // 30 day USD volume keyed by fee schedule uid
+    pub volumes_by_fee_schedule: HashMap<String, f64>,
+}
+
+impl Request for FeeScheduleVolumesRequest {
+    const METHOD: Method = Method::GET;
+    const SIGNED: bool = true;
+    const ENDPOINT: &'static str = "/feeschedules/volumes";
+    const HAS_PAYLOAD: bool = false;
+    const COST: u32 = 2;
+    type Response = FeeScheduleVolumesResponse;
+}
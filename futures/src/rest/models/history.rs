@@ -0,0 +1,345 @@
+use super::Request;
+use crate::common::de::{option_string_or_f64, string_or_f64};
+use crate::common::Side;
+use crate::rest::RatePool;
+use chrono::{serde::ts_milliseconds, serde::ts_milliseconds_option, DateTime, Utc};
+use http::Method;
+use kraken_core::Paginated;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+
+const HISTORY_PATH: &str = "/api/history/v2";
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Sort {
+    Asc,
+    Desc,
+}
+
+// The order, trigger and execution histories take the same parameters and are told apart by their event type.
+#[derive(Serialize, Debug, Clone)]
+pub struct EventsRequest<E> {
+    #[serde(with = "ts_milliseconds_option")]
+    pub since: Option<DateTime<Utc>>,
+    #[serde(with = "ts_milliseconds_option")]
+    pub before: Option<DateTime<Utc>>,
+    pub sort: Option<Sort>,
+    pub tradeable: Option<String>,
+    pub continuation_token: Option<String>,
+    #[serde(skip)]
+    pub event: PhantomData<E>,
+}
+
+pub type OrderEventsRequest = EventsRequest<OrderHistoryEvent>;
+pub type TriggerEventsRequest = EventsRequest<TriggerHistoryEvent>;
+pub type ExecutionEventsRequest = EventsRequest<ExecutionHistoryEvent>;
+
+impl<E> Default for EventsRequest<E> {
+    fn default() -> Self {
+        EventsRequest {
+            since: None,
+            before: None,
+            sort: None,
+            tradeable: None,
+            continuation_token: None,
+            event: PhantomData,
+        }
+    }
+}
+
+pub trait HistoryEvent: DeserializeOwned + Clone {
+    const ENDPOINT: &'static str;
+}
+
+impl HistoryEvent for OrderHistoryEvent {
+    const ENDPOINT: &'static str = "/orders";
+}
+
+impl HistoryEvent for TriggerHistoryEvent {
+    const ENDPOINT: &'static str = "/triggers";
+}
+
+impl HistoryEvent for ExecutionHistoryEvent {
+    const ENDPOINT: &'static str = "/executions";
+}
+
+impl<E: HistoryEvent> Request for EventsRequest<E> {
+    const METHOD: Method = Method::GET;
+    const SIGNED: bool = true;
+    const BASE_PATH: &'static str = HISTORY_PATH;
+    const ENDPOINT: &'static str = E::ENDPOINT;
+    const HAS_PAYLOAD: bool = true;
+    const COST: u32 = 1;
+    const RATE_POOL: RatePool = RatePool::History;
+    type Response = HistoryPage<E>;
+}
+
+impl<E: HistoryEvent> Paginated for EventsRequest<E> {
+    type Page = HistoryPage<E>;
+    type Item = HistoryElement<E>;
+
+    fn next_page(&self, resp: &Self::Page) -> Option<Self> {
+        let token = resp.continuation_token.clone()?;
+        Some(Self {
+            continuation_token: Some(token),
+            ..self.clone()
+        })
+    }
+
+    fn into_items(resp: Self::Page) -> Vec<Self::Item> {
+        resp.elements
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryPage<E> {
+    pub account_uid: String,
+    pub len: u64,
+    pub continuation_token: Option<String>,
+    pub elements: Vec<HistoryElement<E>>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct HistoryElement<E> {
+    pub uid: String,
+    #[serde(with = "ts_milliseconds")]
+    pub timestamp: DateTime<Utc>,
+    pub event: E,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryOrder {
+    pub uid: String,
+    pub account_uid: String,
+    pub tradeable: String,
+    pub direction: Side,
+    #[serde(deserialize_with = "string_or_f64")]
+    pub quantity: f64,
+    #[serde(deserialize_with = "string_or_f64")]
+    pub filled: f64,
+    #[serde(with = "ts_milliseconds")]
+    pub timestamp: DateTime<Utc>,
+    #[serde(default, deserialize_with = "option_string_or_f64")]
+    pub limit_price: Option<f64>,
+    pub order_type: String,
+    pub client_id: Option<String>,
+    pub reduce_only: bool,
+    #[serde(with = "ts_milliseconds")]
+    pub last_update_timestamp: DateTime<Utc>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryTriggerOrder {
+    pub uid: String,
+    pub account_id: Option<u64>,
+    pub tradeable: String,
+    pub direction: Side,
+    #[serde(deserialize_with = "string_or_f64")]
+    pub quantity: f64,
+    #[serde(with = "ts_milliseconds")]
+    pub timestamp: DateTime<Utc>,
+    #[serde(default, deserialize_with = "option_string_or_f64")]
+    pub limit_price: Option<f64>,
+    pub order_type: String,
+    pub client_id: Option<String>,
+    pub reduce_only: bool,
+    #[serde(with = "ts_milliseconds")]
+    pub last_update_timestamp: DateTime<Utc>,
+    #[serde(deserialize_with = "string_or_f64")]
+    pub trigger_price: f64,
+    pub trigger_side: String,
+    pub trigger_signal: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub enum OrderHistoryEvent {
+    #[serde(rename_all = "camelCase")]
+    OrderPlaced {
+        order: HistoryOrder,
+        reason: String,
+        #[serde(default, deserialize_with = "option_string_or_f64")]
+        reduced_quantity: Option<f64>,
+    },
+    #[serde(rename_all = "camelCase")]
+    OrderUpdated {
+        old_order: HistoryOrder,
+        new_order: HistoryOrder,
+        reason: String,
+        #[serde(default, deserialize_with = "option_string_or_f64")]
+        reduced_quantity: Option<f64>,
+    },
+    #[serde(rename_all = "camelCase")]
+    OrderRejected { order: HistoryOrder, reason: String, order_error: String },
+    #[serde(rename_all = "camelCase")]
+    OrderCancelled { order: HistoryOrder, reason: String },
+    #[serde(rename_all = "camelCase")]
+    OrderNotFound { order_id: String, reason: Option<String> },
+    #[serde(rename_all = "camelCase")]
+    OrderEditRejected {
+        attempted_order: HistoryOrder,
+        old_order: HistoryOrder,
+        reason: String,
+        order_error: String,
+    },
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub enum TriggerHistoryEvent {
+    #[serde(rename_all = "camelCase")]
+    OrderTriggerPlaced { order: HistoryTriggerOrder, reason: String },
+    #[serde(rename_all = "camelCase")]
+    OrderTriggerUpdated {
+        old_order: HistoryTriggerOrder,
+        new_order: HistoryTriggerOrder,
+        reason: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    OrderTriggerCancelled { order: HistoryTriggerOrder, reason: String },
+    #[serde(rename_all = "camelCase")]
+    OrderTriggerActivated { order: HistoryTriggerOrder },
+    #[serde(rename_all = "camelCase")]
+    OrderTriggerEditRejected {
+        attempted_order: HistoryTriggerOrder,
+        old_order: HistoryTriggerOrder,
+        reason: String,
+        order_error: String,
+    },
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub enum ExecutionHistoryEvent {
+    #[serde(rename_all = "camelCase")]
+    Execution {
+        execution: HistoryExecution,
+        #[serde(default, deserialize_with = "option_string_or_f64")]
+        taker_reduced_quantity: Option<f64>,
+    },
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryExecution {
+    pub uid: String,
+    pub maker_order: HistoryOrder,
+    pub taker_order: HistoryOrder,
+    #[serde(with = "ts_milliseconds")]
+    pub timestamp: DateTime<Utc>,
+    #[serde(deserialize_with = "string_or_f64")]
+    pub quantity: f64,
+    #[serde(deserialize_with = "string_or_f64")]
+    pub price: f64,
+    #[serde(default, deserialize_with = "option_string_or_f64")]
+    pub mark_price: Option<f64>,
+    pub limit_filled: bool,
+    #[serde(default, deserialize_with = "option_string_or_f64")]
+    pub usd_value: Option<f64>,
+}
+
+// The account log is paged by entry id instead of a continuation token.
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct AccountLogRequest {
+    #[serde(with = "ts_milliseconds_option")]
+    pub since: Option<DateTime<Utc>>,
+    #[serde(with = "ts_milliseconds_option")]
+    pub before: Option<DateTime<Utc>>,
+    pub from: Option<u64>,
+    pub to: Option<u64>,
+    pub sort: Option<Sort>,
+    pub info: Option<String>,
+    pub count: Option<u64>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountLogResponse {
+    pub account_uid: String,
+    pub logs: Vec<AccountLogEntry>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct AccountLogEntry {
+    pub id: u64,
+    pub date: DateTime<Utc>,
+    pub asset: String,
+    pub info: String,
+    pub booking_uid: String,
+    pub margin_account: String,
+    #[serde(default, deserialize_with = "option_string_or_f64")]
+    pub old_balance: Option<f64>,
+    #[serde(default, deserialize_with = "option_string_or_f64")]
+    pub new_balance: Option<f64>,
+    #[serde(default, deserialize_with = "option_string_or_f64")]
+    pub old_average_entry_price: Option<f64>,
+    #[serde(default, deserialize_with = "option_string_or_f64")]
+    pub new_average_entry_price: Option<f64>,
+    #[serde(default, deserialize_with = "option_string_or_f64")]
+    pub trade_price: Option<f64>,
+    #[serde(default, deserialize_with = "option_string_or_f64")]
+    pub mark_price: Option<f64>,
+    #[serde(default, deserialize_with = "option_string_or_f64")]
+    pub realized_pnl: Option<f64>,
+    #[serde(default, deserialize_with = "option_string_or_f64")]
+    pub fee: Option<f64>,
+    pub execution: Option<String>,
+    pub collateral: Option<String>,
+    #[serde(default, deserialize_with = "option_string_or_f64")]
+    pub funding_rate: Option<f64>,
+    #[serde(default, deserialize_with = "option_string_or_f64")]
+    pub realized_funding: Option<f64>,
+    pub contract: Option<String>,
+    #[serde(default, deserialize_with = "option_string_or_f64")]
+    pub conversion_spread_percentage: Option<f64>,
+    #[serde(default, deserialize_with = "option_string_or_f64")]
+    pub liquidation_fee: Option<f64>,
+}
+
+impl Request for AccountLogRequest {
+    const METHOD: Method = Method::GET;
+    const SIGNED: bool = true;
+    const BASE_PATH: &'static str = HISTORY_PATH;
+    const ENDPOINT: &'static str = "/account-log";
+    const HAS_PAYLOAD: bool = true;
+    const RATE_POOL: RatePool = RatePool::History;
+    type Response = AccountLogResponse;
+
+    // The account log is priced by the number of entries requested, 500 when count is not set
+    fn cost(&self) -> u32 {
+        match self.count.unwrap_or(500) {
+            0..=25 => 1,
+            26..=50 => 2,
+            51..=1000 => 3,
+            1001..=5000 => 6,
+            _ => 10,
+        }
+    }
+}
+
+impl Paginated for AccountLogRequest {
+    type Page = AccountLogResponse;
+    type Item = AccountLogEntry;
+
+    fn next_page(&self, resp: &Self::Page) -> Option<Self> {
+        let last = resp.logs.last()?;
+
+        match self.sort {
+            Some(Sort::Asc) => Some(Self {
+                from: Some(last.id + 1),
+                ..self.clone()
+            }),
+            _ if last.id == 0 => None,
+            _ => Some(Self {
+                to: Some(last.id - 1),
+                ..self.clone()
+            }),
+        }
+    }
+
+    fn into_items(resp: Self::Page) -> Vec<Self::Item> {
+        resp.logs
+    }
+}
@@ -0,0 +1,40 @@
+use super::Request;
+use http::Method;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Debug, Clone)]
+pub struct InstrumentsRequest;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct InstrumentsResponse {
+    pub instruments: Vec<Instrument>,
+}
+
+// Symbols are kept as strings since the list contains contracts on currencies `Symbol` doesn't know about.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Instrument {
+    pub symbol: String,
+    pub r#type: String,
+    pub tradeable: bool,
+    pub underlying: Option<String>,
+    pub tick_size: Option<f64>,
+    pub contract_size: Option<f64>,
+    pub impact_mid_size: Option<f64>,
+    pub max_position_size: Option<f64>,
+    pub opening_date: Option<String>,
+    pub last_trading_time: Option<String>,
+    pub funding_rate_coefficient: Option<f64>,
+    pub max_relative_funding_rate: Option<f64>,
+    pub contract_value_trade_precision: Option<i64>,
+    pub post_only: Option<bool>,
+    pub fee_schedule_uid: Option<String>,
+}
+
+impl Request for InstrumentsRequest {
+    const METHOD: Method = Method::GET;
+    const SIGNED: bool = false;
+    const ENDPOINT: &'static str = "/instruments";
+    const HAS_PAYLOAD: bool = false;
+    type Response = InstrumentsResponse;
+}
@@ -0,0 +1,67 @@
+use super::Request;
+use http::Method;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Debug, Clone)]
+pub struct LeveragePreferencesRequest;
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LeveragePreferencesResponse {
+    #[serde(default)]
+    pub leverage_preferences: Vec<LeveragePreference>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LeveragePreference {
+    // A string, as `Symbol` cannot represent every multi-collateral contract
+    pub symbol: String,
+    pub max_leverage: f64,
+}
+
+impl Request for LeveragePreferencesRequest {
+    const METHOD: Method = Method::GET;
+    const SIGNED: bool = true;
+    const ENDPOINT: &'static str = "/leveragepreferences";
+    const HAS_PAYLOAD: bool = false;
+    const COST: u32 = 2;
+    type Response = LeveragePreferencesResponse;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SetLeveragePreferenceRequest {
+    pub symbol: String,
+    // None puts the contract back into cross margin
+    pub max_leverage: Option<f64>,
+}
+
+impl SetLeveragePreferenceRequest {
+    // Takes a `Symbol` as well as any other contract name
+    pub fn isolated<S: ToString>(symbol: S, max_leverage: f64) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            max_leverage: Some(max_leverage),
+        }
+    }
+
+    pub fn cross<S: ToString>(symbol: S) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            max_leverage: None,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct SetLeveragePreferenceResponse {}
+
+impl Request for SetLeveragePreferenceRequest {
+    const METHOD: Method = Method::PUT;
+    const SIGNED: bool = true;
+    const ENDPOINT: &'static str = "/leveragepreferences";
+    const HAS_PAYLOAD: bool = true;
+    const COST: u32 = 10;
+    type Response = SetLeveragePreferenceResponse;
+}
@@ -1,32 +1,74 @@
 mod accounts;
 mod cancel_all_orders;
 mod cancel_order;
+mod fee_schedules;
+mod history;
+mod instruments;
+mod leverage_preferences;
+mod notifications;
+mod open_orders;
 mod open_positions;
 mod orderbook;
+mod pnl_preferences;
 mod send_order;
 mod tickers;
+mod transfer;
+mod withdrawal;
 
 pub use accounts::{AccountDetail, AccountsRequest, AccountsResponse, Auxiliary, MarginRequirements};
 pub use cancel_all_orders::{CancelAllOrdersRequest, CancelAllOrdersResponse, CancelledOrder};
 pub use cancel_order::{CancelOrderRequest, CancelOrderResponse, CancelStatus, Status};
+pub use fee_schedules::{FeeSchedule, FeeScheduleVolumesRequest, FeeScheduleVolumesResponse, FeeSchedulesRequest, FeeSchedulesResponse, FeeTier};
+pub use history::{AccountLogEntry, AccountLogRequest, AccountLogResponse, EventsRequest, HistoryEvent, Sort};
+pub use history::{ExecutionEventsRequest, ExecutionHistoryEvent, HistoryExecution, HistoryOrder, HistoryTriggerOrder};
+pub use history::{HistoryElement, HistoryPage, OrderEventsRequest, OrderHistoryEvent, TriggerEventsRequest, TriggerHistoryEvent};
+pub use instruments::{Instrument, InstrumentsRequest, InstrumentsResponse};
+pub use leverage_preferences::{LeveragePreference, LeveragePreferencesRequest, LeveragePreferencesResponse};
+pub use leverage_preferences::{SetLeveragePreferenceRequest, SetLeveragePreferenceResponse};
+pub use notifications::{Notification, NotificationPriority, NotificationType, NotificationsRequest, NotificationsResponse};
+pub use open_orders::{OpenOrder, OpenOrdersRequest, OpenOrdersResponse};
 pub use open_positions::{OpenPosition, OpenPositionsRequest, OpenPositionsResponse};
 pub use orderbook::{OrderbookRequest, OrderbookResponse};
+pub use pnl_preferences::{PnlPreference, PnlPreferencesRequest, PnlPreferencesResponse, SetPnlPreferenceRequest, SetPnlPreferenceResponse};
 pub use send_order::{SendOrderRequest, SendOrderResponse, SendStatus};
 pub use tickers::{TickersRequest, TickersResponse};
+pub use transfer::{TransferRequest, TransferResponse};
+pub use withdrawal::{WithdrawalRequest, WithdrawalResponse};
 
+use super::{Idempotency, RatePool};
 use http::Method;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
+pub(crate) const DERIVATIVES_PATH: &str = "/derivatives/api/v3";
+
 pub trait Request: Serialize {
     const METHOD: Method;
     const SIGNED: bool = false;
+    const BASE_PATH: &'static str = DERIVATIVES_PATH;
     const ENDPOINT: &'static str;
     const HAS_PAYLOAD: bool = true;
+    // The weight of the request in its rate limit pool. Public endpoints are free.
+    const COST: u32 = 0;
+    const RATE_POOL: RatePool = RatePool::Derivatives;
     type Response: DeserializeOwned;
 
     #[inline]
     fn no_payload(&self) -> bool {
         !Self::HAS_PAYLOAD
     }
+
+    // Override for requests whose cost depends on their content
+    #[inline]
+    fn cost(&self) -> u32 {
+        Self::COST
+    }
+
+    fn idempotency(&self) -> Idempotency {
+        if Self::METHOD == Method::POST {
+            Idempotency::Unsafe
+        } else {
+            Idempotency::Idempotent
+        }
+    }
 }
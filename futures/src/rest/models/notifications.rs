@@ -0,0 +1,72 @@
+use super::Request;
+use crate::common::Symbol;
+use chrono::{DateTime, Utc};
+use http::Method;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Debug, Clone)]
+pub struct NotificationsRequest;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct NotificationsResponse {
+    pub notifications: Vec<Notification>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Notification {
+    pub r#type: NotificationType,
+    pub priority: NotificationPriority,
+    pub note: String,
+    pub effective_time: Option<DateTime<Utc>>,
+    pub expected_downtime_minutes: Option<u64>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationType {
+    Market,
+    Maintenance,
+    Settlement,
+    NewFeature,
+    BugFix,
+    General,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationPriority {
+    Low,
+    Medium,
+    High,
+    // Ranked above the known priorities, so an unexpected one isn't overlooked
+    #[serde(other)]
+    Other,
+}
+
+impl NotificationsResponse {
+    // Notices that mention `symbol` or its pair, plus maintenance notices since those affect every contract.
+    pub fn touching(&self, symbol: Symbol) -> Vec<&Notification> {
+        let symbol_name = symbol.to_string();
+        let pair_name = symbol.pair().map(|p| p.to_string());
+
+        self.notifications
+            .iter()
+            .filter(|n| {
+                let note = n.note.to_uppercase();
+                n.r#type == NotificationType::Maintenance || note.contains(&symbol_name) || pair_name.iter().any(|p| note.contains(p))
+            })
+            .collect()
+    }
+}
+
+impl Request for NotificationsRequest {
+    const METHOD: Method = Method::GET;
+    const SIGNED: bool = true;
+    const ENDPOINT: &'static str = "/notifications";
+    const HAS_PAYLOAD: bool = false;
+    const COST: u32 = 2;
+    type Response = NotificationsResponse;
+}
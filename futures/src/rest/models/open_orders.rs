@@ -0,0 +1,44 @@
+use super::Request;
+use crate::common::{Side, Symbol, TriggerSignal};
+use chrono::{DateTime, Utc};
+use http::Method;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Serialize, Debug, Clone)]
+pub struct OpenOrdersRequest;
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenOrdersResponse {
+    pub open_orders: Vec<OpenOrder>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenOrder {
+    #[serde(rename = "order_id")]
+    pub order_id: Uuid,
+    pub cli_ord_id: Option<Uuid>,
+    pub symbol: Symbol,
+    pub side: Side,
+    pub order_type: String,
+    pub limit_price: Option<f64>,
+    pub stop_price: Option<f64>,
+    pub unfilled_size: f64,
+    pub filled_size: f64,
+    pub received_time: DateTime<Utc>,
+    pub status: String,
+    pub reduce_only: Option<bool>,
+    pub last_update_time: Option<DateTime<Utc>>,
+    pub trigger_signal: Option<TriggerSignal>,
+}
+
+impl Request for OpenOrdersRequest {
+    const METHOD: Method = Method::GET;
+    const SIGNED: bool = true;
+    const ENDPOINT: &'static str = "/openorders";
+    const HAS_PAYLOAD: bool = false;
+    const COST: u32 = 2;
+    type Response = OpenOrdersResponse;
+}
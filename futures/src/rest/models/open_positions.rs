@@ -29,5 +29,6 @@ impl Request for OpenPositionsRequest {
     const SIGNED: bool = true;
     const ENDPOINT: &'static str = "/openpositions";
     const HAS_PAYLOAD: bool = false;
+    const COST: u32 = 2;
     type Response = OpenPositionsResponse;
 }
@@ -0,0 +1,60 @@
+use super::Request;
+use http::Method;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Debug, Clone)]
+pub struct PnlPreferencesRequest;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct PnlPreferencesResponse {
+    #[serde(default)]
+    pub preferences: Vec<PnlPreference>,
+}
+
+// Plain strings, since multi-collateral contracts such as PF_SOLUSD settle in currencies like USDT or EUR
+// that `Symbol` and `Currency` cannot represent.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PnlPreference {
+    pub symbol: String,
+    pub pnl_currency: String,
+}
+
+impl Request for PnlPreferencesRequest {
+    const METHOD: Method = Method::GET;
+    const SIGNED: bool = true;
+    const ENDPOINT: &'static str = "/pnlpreferences";
+    const HAS_PAYLOAD: bool = false;
+    const COST: u32 = 2;
+    type Response = PnlPreferencesResponse;
+}
+
+// Chooses the currency that PnL of a multi-collateral contract is settled in.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SetPnlPreferenceRequest {
+    pub symbol: String,
+    pub pnl_preference: String,
+}
+
+impl SetPnlPreferenceRequest {
+    // Takes a `Symbol` and `Currency` as well as any other name Kraken knows
+    pub fn new<S: ToString, C: ToString>(symbol: S, pnl_preference: C) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            pnl_preference: pnl_preference.to_string(),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct SetPnlPreferenceResponse {}
+
+impl Request for SetPnlPreferenceRequest {
+    const METHOD: Method = Method::PUT;
+    const SIGNED: bool = true;
+    const ENDPOINT: &'static str = "/pnlpreferences";
+    const HAS_PAYLOAD: bool = true;
+    const COST: u32 = 10;
+    type Response = SetPnlPreferenceResponse;
+}
@@ -1,37 +1,146 @@
 use super::Request;
-use crate::common::{OrderEvent, OrderType, SendOrderStatus, Side, Symbol, TriggerSignal};
+use crate::common::{OrderEvent, OrderType, SelfTradeStrategy, SendOrderStatus, Side, Symbol, TrailingStopDeviationUnit, TriggerSignal};
+use crate::errors::KrakenError;
+use crate::rest::Idempotency;
 use chrono::{DateTime, Utc};
+use fehler::{throw, throws};
 use http::Method;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+// Construct with one of the order type constructors, which take exactly the fields that order type requires.
 #[derive(Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct SendOrderRequest {
-    pub order_type: OrderType,
-    pub symbol: Symbol,
-    pub side: Side,
-    pub size: u64,
-    pub limit_price: f64,
-    pub stop_price: Option<f64>,
-    pub trigger_signal: Option<TriggerSignal>,
-    pub cli_ord_id: Option<Uuid>,
-    pub reduce_only: Option<bool>,
+    order_type: OrderType,
+    symbol: Symbol,
+    side: Side,
+    size: u64,
+    limit_price: Option<f64>,
+    stop_price: Option<f64>,
+    trigger_signal: Option<TriggerSignal>,
+    trailing_stop_max_deviation: Option<f64>,
+    trailing_stop_deviation_unit: Option<TrailingStopDeviationUnit>,
+    cli_ord_id: Option<Uuid>,
+    reduce_only: Option<bool>,
+    self_trade_strategy: Option<SelfTradeStrategy>,
 }
 
 impl SendOrderRequest {
-    pub fn limit(symbol: Symbol, price: f64, qty: i64) -> Self {
+    // A positive qty buys and a negative qty sells. Zero has no side and is rejected.
+    #[throws(KrakenError)]
+    fn new(order_type: OrderType, symbol: Symbol, qty: i64) -> Self {
+        if qty == 0 {
+            throw!(KrakenError::InvalidOrder("size must not be zero".into()));
+        }
         let side = if qty > 0 { Side::Buy } else { Side::Sell };
         Self {
-            order_type: OrderType::Lmt,
-            symbol: symbol,
-            side: side,
-            size: qty.abs() as u64,
-            limit_price: price,
+            order_type,
+            symbol,
+            side,
+            size: qty.unsigned_abs(),
+            limit_price: None,
             stop_price: None,
             trigger_signal: None,
+            trailing_stop_max_deviation: None,
+            trailing_stop_deviation_unit: None,
             cli_ord_id: None,
             reduce_only: None,
+            self_trade_strategy: None,
+        }
+    }
+
+    #[throws(KrakenError)]
+    pub fn limit(symbol: Symbol, price: f64, qty: i64) -> Self {
+        Self {
+            limit_price: Some(price),
+            ..Self::new(OrderType::Lmt, symbol, qty)?
+        }
+    }
+
+    #[throws(KrakenError)]
+    pub fn post_only(symbol: Symbol, price: f64, qty: i64) -> Self {
+        Self {
+            limit_price: Some(price),
+            ..Self::new(OrderType::Post, symbol, qty)?
+        }
+    }
+
+    #[throws(KrakenError)]
+    pub fn ioc(symbol: Symbol, price: f64, qty: i64) -> Self {
+        Self {
+            limit_price: Some(price),
+            ..Self::new(OrderType::IOC, symbol, qty)?
+        }
+    }
+
+    #[throws(KrakenError)]
+    pub fn market(symbol: Symbol, qty: i64) -> Self {
+        Self::new(OrderType::Mkt, symbol, qty)?
+    }
+
+    #[throws(KrakenError)]
+    pub fn stop(symbol: Symbol, stop_price: f64, trigger_signal: TriggerSignal, qty: i64) -> Self {
+        Self {
+            stop_price: Some(stop_price),
+            trigger_signal: Some(trigger_signal),
+            ..Self::new(OrderType::Stp, symbol, qty)?
+        }
+    }
+
+    #[throws(KrakenError)]
+    pub fn stop_limit(symbol: Symbol, stop_price: f64, limit_price: f64, trigger_signal: TriggerSignal, qty: i64) -> Self {
+        Self {
+            limit_price: Some(limit_price),
+            ..Self::stop(symbol, stop_price, trigger_signal, qty)?
+        }
+    }
+
+    #[throws(KrakenError)]
+    pub fn take_profit(symbol: Symbol, stop_price: f64, trigger_signal: TriggerSignal, qty: i64) -> Self {
+        Self {
+            stop_price: Some(stop_price),
+            trigger_signal: Some(trigger_signal),
+            ..Self::new(OrderType::TakeProfit, symbol, qty)?
+        }
+    }
+
+    #[throws(KrakenError)]
+    pub fn take_profit_limit(symbol: Symbol, stop_price: f64, limit_price: f64, trigger_signal: TriggerSignal, qty: i64) -> Self {
+        Self {
+            limit_price: Some(limit_price),
+            ..Self::take_profit(symbol, stop_price, trigger_signal, qty)?
+        }
+    }
+
+    #[throws(KrakenError)]
+    pub fn trailing_stop(symbol: Symbol, max_deviation: f64, deviation_unit: TrailingStopDeviationUnit, trigger_signal: TriggerSignal, qty: i64) -> Self {
+        Self {
+            trailing_stop_max_deviation: Some(max_deviation),
+            trailing_stop_deviation_unit: Some(deviation_unit),
+            trigger_signal: Some(trigger_signal),
+            ..Self::new(OrderType::TrailingStop, symbol, qty)?
+        }
+    }
+
+    pub fn cli_ord_id(self, cli_ord_id: Uuid) -> Self {
+        Self {
+            cli_ord_id: Some(cli_ord_id),
+            ..self
+        }
+    }
+
+    pub fn reduce_only(self, reduce_only: bool) -> Self {
+        Self {
+            reduce_only: Some(reduce_only),
+            ..self
+        }
+    }
+
+    pub fn self_trade_strategy(self, self_trade_strategy: SelfTradeStrategy) -> Self {
+        Self {
+            self_trade_strategy: Some(self_trade_strategy),
+            ..self
         }
     }
 }
@@ -79,5 +188,15 @@ impl Request for SendOrderRequest {
     const SIGNED: bool = true;
     const ENDPOINT: &'static str = "/sendorder";
     const HAS_PAYLOAD: bool = true;
+    const COST: u32 = 10;
     type Response = SendOrderResponse;
+
+    // Kraken rejects a second order with the same cli_ord_id. Without one, a resent order cannot be told
+    // apart from the first, so it is never resent once it may have reached Kraken.
+    fn idempotency(&self) -> Idempotency {
+        match self.cli_ord_id {
+            Some(_) => Idempotency::Idempotent,
+            None => Idempotency::Unsafe,
+        }
+    }
 }
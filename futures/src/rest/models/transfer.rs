@@ -0,0 +1,42 @@
+use super::Request;
+use crate::common::de::lowercase;
+use crate::common::Currency;
+use http::Method;
+use serde::{Deserialize, Serialize};
+
+// Moves funds between the accounts returned by `AccountsRequest`, e.g. "cash", "flex" or a margin account like "fi_xbtusd".
+// Account names are plain strings as they are not all contract symbols.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferRequest {
+    #[serde(serialize_with = "lowercase")]
+    pub from_account: String,
+    #[serde(serialize_with = "lowercase")]
+    pub to_account: String,
+    #[serde(serialize_with = "lowercase")]
+    pub unit: Currency,
+    pub amount: f64,
+}
+
+impl TransferRequest {
+    pub fn new<F: ToString, T: ToString>(from_account: F, to_account: T, unit: Currency, amount: f64) -> Self {
+        Self {
+            from_account: from_account.to_string(),
+            to_account: to_account.to_string(),
+            unit,
+            amount,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TransferResponse {}
+
+impl Request for TransferRequest {
+    const METHOD: Method = Method::POST;
+    const SIGNED: bool = true;
+    const ENDPOINT: &'static str = "/transfer";
+    const HAS_PAYLOAD: bool = true;
+    const COST: u32 = 10;
+    type Response = TransferResponse;
+}
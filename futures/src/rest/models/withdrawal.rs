@@ -0,0 +1,33 @@
+use super::Request;
+use crate::common::de::lowercase;
+use crate::common::Currency;
+use http::Method;
+use serde::{Deserialize, Serialize};
+
+// Sends funds from the futures cash account to the Kraken spot wallet.
+#[derive(Serialize, Debug, Clone)]
+pub struct WithdrawalRequest {
+    #[serde(serialize_with = "lowercase")]
+    pub currency: Currency,
+    pub amount: f64,
+}
+
+impl WithdrawalRequest {
+    pub fn new(currency: Currency, amount: f64) -> Self {
+        Self { currency, amount }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct WithdrawalResponse {
+    pub uid: Option<String>,
+}
+
+impl Request for WithdrawalRequest {
+    const METHOD: Method = Method::POST;
+    const SIGNED: bool = true;
+    const ENDPOINT: &'static str = "/withdrawal";
+    const HAS_PAYLOAD: bool = true;
+    const COST: u32 = 100;
+    type Response = WithdrawalResponse;
+}
@@ -0,0 +1,7 @@
+use kraken_core::Nanos;
+
+pub use kraken_core::NonceSource;
+
+// Kraken Futures takes nonces in nanoseconds
+pub type MonotonicNonce = kraken_core::MonotonicNonce<Nanos>;
+pub type PersistentNonce = kraken_core::PersistentNonce<Nanos>;
@@ -0,0 +1,111 @@
+use crate::errors::KrakenError;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::time::delay_for;
+
+// Kraken Futures budgets each pool separately, see https://docs.futures.kraken.com/#http-api-rate-limits
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RatePool {
+    // The /derivatives/api/v3 endpoints, 500 per 10 seconds
+    Derivatives,
+    // The /api/history/v2 endpoints, 100 per 10 minutes
+    History,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitMode {
+    // Wait until the pool has enough budget for the request
+    Queue,
+    // Fail with `KrakenError::RateLimited` if the pool doesn't have enough budget
+    Reject,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    capacity: f64,
+    window: Duration,
+    available: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    fn new(capacity: u32, window: Duration) -> Self {
+        Bucket {
+            capacity: capacity as f64,
+            window,
+            available: capacity as f64,
+            updated: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.duration_since(self.updated).as_secs_f64();
+        self.available = (self.available + elapsed * self.capacity / self.window.as_secs_f64()).min(self.capacity);
+        self.updated = now;
+    }
+
+    // How long until `cost` fits into the bucket
+    fn wait_for(&self, cost: f64) -> Duration {
+        Duration::from_secs_f64((cost - self.available).max(0.) * self.window.as_secs_f64() / self.capacity)
+    }
+}
+
+// A client-side token bucket per pool. Clones share the same budget, so a limiter can be handed
+// to several `KrakenRest` clients that use the same API key.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    mode: RateLimitMode,
+    buckets: Arc<Mutex<HashMap<RatePool, Bucket>>>,
+}
+
+impl RateLimiter {
+    // A limiter with Kraken's documented budgets
+    pub fn new(mode: RateLimitMode) -> Self {
+        let mut buckets = HashMap::new();
+        buckets.insert(RatePool::Derivatives, Bucket::new(500, Duration::from_secs(10)));
+        buckets.insert(RatePool::History, Bucket::new(100, Duration::from_secs(600)));
+
+        RateLimiter {
+            mode,
+            buckets: Arc::new(Mutex::new(buckets)),
+        }
+    }
+
+    // Overrides the budget of a pool, e.g. to leave headroom for another process using the same key
+    pub fn with_budget(self, pool: RatePool, capacity: u32, window: Duration) -> Self {
+        self.buckets.lock().unwrap().insert(pool, Bucket::new(capacity, window));
+        self
+    }
+
+    // Takes `cost` from the pool, waiting for it to refill in queue mode
+    pub async fn acquire(&self, pool: RatePool, cost: u32) -> Result<(), KrakenError> {
+        if cost == 0 {
+            return Ok(());
+        }
+
+        loop {
+            let wait = {
+                let mut buckets = self.buckets.lock().unwrap();
+                let bucket = match buckets.get_mut(&pool) {
+                    Some(bucket) => bucket,
+                    None => return Ok(()),
+                };
+                // A request costing more than the whole budget waits for a full bucket
+                let cost = (cost as f64).min(bucket.capacity);
+
+                bucket.refill(Instant::now());
+                if bucket.available >= cost {
+                    bucket.available -= cost;
+                    return Ok(());
+                }
+                bucket.wait_for(cost)
+            };
+
+            match self.mode {
+                RateLimitMode::Queue => delay_for(wait).await,
+                RateLimitMode::Reject => return Err(KrakenError::RateLimited { pool, retry_after: wait }),
+            }
+        }
+    }
+}
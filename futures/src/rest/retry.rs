@@ -0,0 +1,80 @@
+use crate::errors::KrakenError;
+use ring::rand::{SecureRandom, SystemRandom};
+use std::time::Duration;
+
+// Whether a request that may have reached Kraken can be sent again
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Idempotency {
+    // Sending it twice has the same effect as sending it once, e.g. reads, cancels and orders with a cli_ord_id
+    Idempotent,
+    // Never sent again once it may have reached Kraken
+    Unsafe,
+}
+
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    // Including the first attempt
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub multiplier: f64,
+    // Randomizes each backoff between half and all of its length
+    pub jitter: bool,
+    pub retryable: fn(&KrakenError) -> bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+            multiplier: 2.,
+            jitter: true,
+            retryable: RetryPolicy::default_retryable,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn never() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    // Transient exchange errors, transport failures and server errors
+    pub fn default_retryable(error: &KrakenError) -> bool {
+        match error {
+            KrakenError::Api { error, .. } => error.is_retryable(),
+            KrakenError::RateLimited { .. } | KrakenError::Transport(_) => true,
+            KrakenError::CannotDeserializeResponse { status, .. } => status.is_server_error(),
+            _ => false,
+        }
+    }
+
+    // The delay before attempt number `attempt + 1`
+    pub fn backoff(&self, attempt: u32, error: &KrakenError) -> Duration {
+        let backoff = self.initial_backoff.as_secs_f64() * self.multiplier.powi(attempt.saturating_sub(1) as i32);
+        let mut backoff = Duration::from_secs_f64(backoff.min(self.max_backoff.as_secs_f64()));
+
+        if self.jitter {
+            let mut bytes = [0u8; 4];
+            if SystemRandom::new().fill(&mut bytes).is_ok() {
+                let ratio = u32::from_le_bytes(bytes) as f64 / u32::MAX as f64;
+                backoff = backoff.mul_f64(0.5 + ratio / 2.);
+            }
+        }
+
+        match error {
+            KrakenError::RateLimited { retry_after, .. } => backoff.max(*retry_after),
+            _ => backoff,
+        }
+    }
+}
+
+// Errors after which the request may or may not have been executed by Kraken
+pub(crate) fn in_flight(error: &KrakenError) -> bool {
+    matches!(error, KrakenError::Transport(_) | KrakenError::CannotDeserializeResponse { .. })
+}
@@ -1,3 +1,4 @@
+use crate::errors::KrakenError;
 use crate::Symbol;
 use fehler::{throw, throws};
 use serde::{ser, Serialize, Serializer};
@@ -35,7 +36,7 @@ impl Command {
         Self::Heartbeat
     }
 
-    #[throws(failure::Error)]
+    #[throws(KrakenError)]
     pub fn trade(product_ids: &[&str]) -> Self {
         Self::Trade {
             product_ids: product_ids.iter().map(|s| s.parse()).collect::<Result<Vec<_>, _>>()?,
@@ -0,0 +1,56 @@
+// Shared by the offline tests, each of which uses a different part of it
+#![allow(dead_code)]
+
+use futures::future::{ready, BoxFuture, FutureExt};
+use http::StatusCode;
+use kraken_futures::errors::KrakenError;
+use kraken_futures::rest::{HttpRequest, HttpResponse, Transport};
+use std::sync::{Arc, Mutex};
+
+// Answers requests with canned responses in turn, repeating the last one, and remembers what was sent
+#[derive(Clone)]
+pub struct FakeTransport {
+    responses: Vec<(StatusCode, String)>,
+    sent: Arc<Mutex<Vec<HttpRequest>>>,
+}
+
+impl FakeTransport {
+    pub fn new(status: StatusCode, body: &str) -> Self {
+        Self::script(vec![(status, body)])
+    }
+
+    pub fn script(responses: Vec<(StatusCode, &str)>) -> Self {
+        FakeTransport {
+            responses: responses.into_iter().map(|(status, body)| (status, body.to_string())).collect(),
+            sent: Arc::new(Mutex::new(vec![])),
+        }
+    }
+
+    pub fn sent(&self) -> Vec<HttpRequest> {
+        self.sent.lock().unwrap().clone()
+    }
+
+    // The paths and queries of the requests sent so far, without the nonce
+    pub fn urls(&self) -> Vec<String> {
+        self.sent()
+            .iter()
+            .map(|r| {
+                let query: Vec<_> = r.url.query().unwrap_or_default().split('&').filter(|p| !p.starts_with("nonce=")).collect();
+                format!("{}?{}", r.url.path(), query.join("&"))
+            })
+            .collect()
+    }
+}
+
+impl Transport<KrakenError> for FakeTransport {
+    fn send(&self, req: HttpRequest) -> BoxFuture<'static, Result<HttpResponse, KrakenError>> {
+        let mut sent = self.sent.lock().unwrap();
+        let (status, body) = self.responses[sent.len().min(self.responses.len() - 1)].clone();
+        sent.push(req);
+        ready(Ok(HttpResponse { status, body })).boxed()
+    }
+}
+
+pub fn header<'a>(req: &'a HttpRequest, name: &str) -> Option<&'a str> {
+    req.headers.iter().find(|(n, _)| n == name).map(|(_, v)| v.as_str())
+}
@@ -0,0 +1,144 @@
+mod common;
+
+use chrono::{TimeZone, Utc};
+use common::FakeTransport;
+use futures::stream::{StreamExt, TryStreamExt};
+use http::StatusCode;
+use kraken_futures::rest::{AccountLogRequest, KrakenRest, OrderEventsRequest, OrderHistoryEvent, Sort};
+
+const ORDER: &str = r#"{"uid":"2c42a6d0-fb01-4b4f-a5d0-2a80b4d7b4f7","accountUid":"f7d5571c-6d10-4cf1-944a-048d25682ed0","tradeable":"PF_XBTUSD","direction":"Buy",
+    "quantity":"0.01","filled":"0","timestamp":1604937694000,"limitPrice":"15000","orderType":"Limit","clientId":"","reduceOnly":false,"lastUpdateTimestamp":1604937694000}"#;
+
+fn client(transport: &FakeTransport) -> KrakenRest {
+    KrakenRest::with_credential(None, "key", "c2VjcmV0").with_transport(transport.clone())
+}
+
+fn events_page(uids: &[&str], token: Option<&str>) -> String {
+    let elements: Vec<_> = uids
+        .iter()
+        .map(|uid| {
+            format!(
+                r#"{{"uid":"{}","timestamp":1604937694000,"event":{{"OrderPlaced":{{"order":{},"reason":"new_user_order"}}}}}}"#,
+                uid, ORDER
+            )
+        })
+        .collect();
+    let token = token.map(|t| format!(r#""{}""#, t)).unwrap_or_else(|| "null".to_string());
+    format!(
+        r#"{{"accountUid":"f7d5571c-6d10-4cf1-944a-048d25682ed0","len":{},"continuationToken":{},"elements":[{}]}}"#,
+        uids.len(),
+        token,
+        elements.join(",")
+    )
+}
+
+fn log_page(ids: &[u64]) -> String {
+    let logs: Vec<_> = ids
+        .iter()
+        .map(|id| {
+            format!(
+                r#"{{"id":{},"date":"2020-11-09T16:01:34.000Z","asset":"xbt","info":"futures trade","booking_uid":"b{}","margin_account":"f-xbt:usd","old_balance":"1.0","new_balance":"1.1"}}"#,
+                id, id
+            )
+        })
+        .collect();
+    format!(r#"{{"accountUid":"f7d5571c-6d10-4cf1-944a-048d25682ed0","logs":[{}]}}"#, logs.join(","))
+}
+
+#[tokio::test]
+async fn test_continuation_token_paging() {
+    let (first, last) = (events_page(&["e1", "e2"], Some("c2")), events_page(&["e3"], None));
+    let transport = FakeTransport::script(vec![(StatusCode::OK, &first), (StatusCode::OK, &last)]);
+
+    let req = OrderEventsRequest {
+        since: Some(Utc.timestamp_millis(1604937600000)),
+        sort: Some(Sort::Asc),
+        ..OrderEventsRequest::default()
+    };
+    let events: Vec<_> = client(&transport).stream(req).try_collect().await.unwrap();
+
+    assert_eq!(events.iter().map(|e| e.uid.as_str()).collect::<Vec<_>>(), vec!["e1", "e2", "e3"]);
+    match &events[0].event {
+        OrderHistoryEvent::OrderPlaced { order, reason, .. } => {
+            assert_eq!(order.quantity, 0.01);
+            assert_eq!(reason, "new_user_order");
+        }
+        e => panic!("unexpected {:?}", e),
+    }
+    // The stream ends with the page that has no continuation token
+    assert_eq!(
+        transport.urls(),
+        vec![
+            "/api/history/v2/orders?since=1604937600000&sort=asc",
+            "/api/history/v2/orders?continuation_token=c2&since=1604937600000&sort=asc",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_account_log_paging() {
+    let pages = [log_page(&[5, 4]), log_page(&[3]), log_page(&[])];
+    let transport = FakeTransport::script(pages.iter().map(|p| (StatusCode::OK, p.as_str())).collect());
+
+    let req = AccountLogRequest {
+        count: Some(2),
+        ..AccountLogRequest::default()
+    };
+    let entries: Vec<_> = client(&transport).stream(req).try_collect().await.unwrap();
+
+    assert_eq!(entries.iter().map(|e| e.id).collect::<Vec<_>>(), vec![5, 4, 3]);
+    assert_eq!(entries[0].new_balance, Some(1.1));
+    // Newest first, each page ends below the last id seen, until a page comes back empty
+    assert_eq!(
+        transport.urls(),
+        vec![
+            "/api/history/v2/account-log?count=2",
+            "/api/history/v2/account-log?count=2&to=3",
+            "/api/history/v2/account-log?count=2&to=2"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_account_log_paging_ascending() {
+    let pages = [log_page(&[0, 1]), log_page(&[])];
+    let transport = FakeTransport::script(pages.iter().map(|p| (StatusCode::OK, p.as_str())).collect());
+
+    let req = AccountLogRequest {
+        sort: Some(Sort::Asc),
+        ..AccountLogRequest::default()
+    };
+    let entries: Vec<_> = client(&transport).stream(req).try_collect().await.unwrap();
+
+    assert_eq!(entries.len(), 2);
+    assert_eq!(
+        transport.urls(),
+        vec!["/api/history/v2/account-log?sort=asc", "/api/history/v2/account-log?from=2&sort=asc"]
+    );
+}
+
+#[tokio::test]
+async fn test_account_log_stops_at_first_entry() {
+    let page = log_page(&[1, 0]);
+    let transport = FakeTransport::new(StatusCode::OK, &page);
+
+    let entries: Vec<_> = client(&transport).stream(AccountLogRequest::default()).try_collect().await.unwrap();
+
+    // Nothing can come before id 0, so no further page is requested
+    assert_eq!(entries.len(), 2);
+    assert_eq!(transport.sent().len(), 1);
+}
+
+#[tokio::test]
+async fn test_stream_ends_on_error() {
+    let transport = FakeTransport::script(vec![
+        (StatusCode::OK, &events_page(&["e1"], Some("c2"))),
+        (StatusCode::OK, r#"{"result":"error","error":"apiLimitExceeded"}"#),
+    ]);
+
+    let results: Vec<_> = client(&transport).stream(OrderEventsRequest::default()).collect::<Vec<_>>().await;
+
+    assert_eq!(results.len(), 2);
+    assert!(results[0].is_ok() && results[1].is_err());
+    assert_eq!(transport.sent().len(), 2);
+}
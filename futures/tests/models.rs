@@ -0,0 +1,272 @@
+mod common;
+
+use common::FakeTransport;
+use http::StatusCode;
+use kraken_futures::errors::KrakenError;
+use kraken_futures::rest::SendOrderRequest;
+use kraken_futures::rest::{FeeScheduleVolumesResponse, FeeSchedulesResponse, FeeTier, InstrumentsResponse};
+use kraken_futures::rest::{KrakenRest, TransferRequest, WithdrawalRequest};
+use kraken_futures::rest::{LeveragePreferencesRequest, PnlPreferencesRequest, SetLeveragePreferenceRequest, SetPnlPreferenceRequest};
+use kraken_futures::rest::{NotificationPriority, NotificationType, NotificationsRequest};
+use kraken_futures::{Currency, OrderEvent, Symbol, TrailingStopDeviationUnit, TriggerSignal};
+use serde_json::{from_str, json, to_value, Value};
+use uuid::Uuid;
+
+fn client(transport: &FakeTransport) -> KrakenRest {
+    KrakenRest::with_credential(None, "key", "c2VjcmV0").with_transport(transport.clone())
+}
+
+// The parameters of the last request without the nonce, from the body or for GET and PUT the query
+fn params(transport: &FakeTransport) -> String {
+    let req = transport.sent().pop().unwrap();
+    let params = req.url.query().map(str::to_string).unwrap_or(req.body);
+    params.split('&').filter(|p| !p.starts_with("nonce=")).collect::<Vec<_>>().join("&")
+}
+
+#[tokio::test]
+async fn test_transfer() {
+    let transport = FakeTransport::new(StatusCode::OK, r#"{"result":"success","serverTime":"2020-11-09T16:01:34.000Z"}"#);
+
+    let symbol: Symbol = "FI_XBTUSD".parse().unwrap();
+    client(&transport).request(TransferRequest::new(symbol, "cash", Currency::XBT, 0.5)).await.unwrap();
+    assert_eq!(params(&transport), "amount=0.5&fromAccount=fi_xbtusd&toAccount=cash&unit=xbt");
+
+    // Accounts that are not contract symbols
+    client(&transport).request(TransferRequest::new("Flex", "cash", Currency::USD, 100.)).await.unwrap();
+    assert_eq!(params(&transport), "amount=100.0&fromAccount=flex&toAccount=cash&unit=usd");
+}
+
+#[tokio::test]
+async fn test_transfer_error() {
+    // TransferResponse has no fields, so the error body must not be taken for a success
+    let transport = FakeTransport::new(
+        StatusCode::OK,
+        r#"{"result":"error","serverTime":"2020-11-09T16:01:34.000Z","error":"insufficientAvailableFunds"}"#,
+    );
+
+    match client(&transport).request(TransferRequest::new("cash", "flex", Currency::USD, 100.)).await {
+        Err(KrakenError::Api { message, .. }) => assert_eq!(message, "insufficientAvailableFunds"),
+        r => panic!("unexpected {:?}", r),
+    }
+}
+
+#[tokio::test]
+async fn test_withdrawal() {
+    let transport = FakeTransport::new(StatusCode::OK, r#"{"result":"success","uid":"3d1d3c7e-3c2e-4a59-9b89-5b7d3b0c5d2f"}"#);
+
+    let resp = client(&transport).request(WithdrawalRequest::new(Currency::ETH, 2.)).await.unwrap();
+
+    assert_eq!(params(&transport), "amount=2.0&currency=eth");
+    assert_eq!(resp.uid.as_deref(), Some("3d1d3c7e-3c2e-4a59-9b89-5b7d3b0c5d2f"));
+}
+
+#[tokio::test]
+async fn test_pnl_preferences() {
+    let transport = FakeTransport::new(
+        StatusCode::OK,
+        r#"{"result":"success","preferences":[{"symbol":"PF_XBTUSD","pnlCurrency":"USDT"},{"symbol":"PF_SOLUSD","pnlCurrency":"EUR"}]}"#,
+    );
+
+    let resp = client(&transport).request(PnlPreferencesRequest).await.unwrap();
+    assert_eq!(resp.preferences[1].symbol, "PF_SOLUSD");
+    assert_eq!(resp.preferences[1].pnl_currency, "EUR");
+
+    let symbol: Symbol = "PF_XBTUSD".parse().unwrap();
+    client(&transport).request(SetPnlPreferenceRequest::new(symbol, "USDC")).await.unwrap();
+    assert_eq!(params(&transport), "pnlPreference=USDC&symbol=PF_XBTUSD");
+}
+
+#[tokio::test]
+async fn test_leverage_preferences() {
+    let transport = FakeTransport::new(
+        StatusCode::OK,
+        r#"{"result":"success","leveragePreferences":[{"symbol":"PF_XBTUSD","maxLeverage":5.0},{"symbol":"PF_1INCHUSD","maxLeverage":2.0}]}"#,
+    );
+
+    let resp = client(&transport).request(LeveragePreferencesRequest).await.unwrap();
+    assert_eq!(resp.leverage_preferences[1].symbol, "PF_1INCHUSD");
+    assert_eq!(resp.leverage_preferences[1].max_leverage, 2.);
+
+    client(&transport).request(SetLeveragePreferenceRequest::isolated("PF_SOLUSD", 3.)).await.unwrap();
+    assert_eq!(params(&transport), "maxLeverage=3.0&symbol=PF_SOLUSD");
+}
+
+#[test]
+fn test_tier_for() {
+    let schedules: FeeSchedulesResponse = from_str(
+        r#"{"feeSchedules":[{"uid":"7fc4d7c0","name":"PF Schedule","tiers":[
+            {"makerFee":0.02,"takerFee":0.05,"usdVolume":100000.0},
+            {"makerFee":0.015,"takerFee":0.04,"usdVolume":1000000.0},
+            {"makerFee":0.0,"takerFee":0.03,"usdVolume":5000000.0}]}]}"#,
+    )
+    .unwrap();
+    let instruments: InstrumentsResponse = from_str(r#"{"instruments":[{"symbol":"pf_xbtusd","type":"flexible_futures","tradeable":true,"feeScheduleUid":"7fc4d7c0"}]}"#).unwrap();
+    let symbol: Symbol = "PF_XBTUSD".parse().unwrap();
+
+    let tier = |volume: f64| {
+        let volumes: FeeScheduleVolumesResponse = from_str(&format!(r#"{{"volumesByFeeSchedule":{{"7fc4d7c0":{}}}}}"#, volume)).unwrap();
+        schedules.tier_for(symbol, &instruments, &volumes).map(|t: FeeTier| t.taker_fee)
+    };
+
+    // Below the first threshold the base tier applies, each tier starts at exactly its volume
+    assert_eq!(tier(0.), Some(0.05));
+    assert_eq!(tier(99999.), Some(0.05));
+    assert_eq!(tier(100000.), Some(0.05));
+    assert_eq!(tier(999999.), Some(0.05));
+    assert_eq!(tier(1000000.), Some(0.04));
+    assert_eq!(tier(1e9), Some(0.03));
+
+    // Without a volume for the schedule, e.g. a fresh account, the volume is taken as 0
+    let volumes: FeeScheduleVolumesResponse = from_str(r#"{"volumesByFeeSchedule":{}}"#).unwrap();
+    assert_eq!(schedules.tier_for(symbol, &instruments, &volumes).map(|t| t.taker_fee), Some(0.05));
+
+    // Unknown symbols have no tier
+    let other: Symbol = "PF_ETHUSD".parse().unwrap();
+    assert_eq!(schedules.tier_for(other, &instruments, &volumes), None);
+}
+
+#[tokio::test]
+async fn test_notifications() {
+    let transport = FakeTransport::new(
+        StatusCode::OK,
+        r#"{"result":"success","serverTime":"2020-11-09T16:01:34.000Z","notifications":[
+            {"type":"market","priority":"low","note":"PF_XBTUSD tick size changes"},
+            {"type":"maintenance","priority":"high","note":"Scheduled maintenance","effectiveTime":"2020-11-10T08:00:00.000Z","expectedDowntimeMinutes":30},
+            {"type":"settlement","priority":"critical","note":"FI_ETHUSD settles today"},
+            {"type":"regulatory","priority":"medium","note":"Terms updated"}]}"#,
+    );
+
+    let resp = client(&transport).request(NotificationsRequest).await.unwrap();
+    let priorities: Vec<_> = resp.notifications.iter().map(|n| n.priority).collect();
+    assert_eq!(
+        priorities,
+        vec![
+            NotificationPriority::Low,
+            NotificationPriority::High,
+            NotificationPriority::Other,
+            NotificationPriority::Medium
+        ]
+    );
+    assert_eq!(resp.notifications[3].r#type, NotificationType::Other);
+    assert_eq!(resp.notifications[1].expected_downtime_minutes, Some(30));
+
+    let touching: Vec<_> = resp.touching("PF_XBTUSD".parse().unwrap()).iter().map(|n| n.note.as_str()).collect();
+    assert_eq!(touching, vec!["PF_XBTUSD tick size changes", "Scheduled maintenance"]);
+}
+
+// The serialized fields of an order, without the unset ones
+fn order_fields(req: &SendOrderRequest) -> Value {
+    match to_value(req).unwrap() {
+        Value::Object(fields) => Value::Object(fields.into_iter().filter(|(_, v)| !v.is_null()).collect()),
+        v => v,
+    }
+}
+
+#[test]
+fn test_send_order_constructors() {
+    let symbol: Symbol = "PF_XBTUSD".parse().unwrap();
+
+    assert_eq!(
+        order_fields(&SendOrderRequest::market(symbol, -3).unwrap()),
+        json!({"orderType": "mkt", "symbol": "PF_XBTUSD", "side": "sell", "size": 3})
+    );
+    assert_eq!(
+        order_fields(&SendOrderRequest::limit(symbol, 30000., 2).unwrap()),
+        json!({"orderType": "lmt", "symbol": "PF_XBTUSD", "side": "buy", "size": 2, "limitPrice": 30000.0})
+    );
+    assert_eq!(order_fields(&SendOrderRequest::post_only(symbol, 30000., 2).unwrap())["orderType"], "post");
+    assert_eq!(order_fields(&SendOrderRequest::ioc(symbol, 30000., 2).unwrap())["orderType"], "ioc");
+    assert_eq!(
+        order_fields(&SendOrderRequest::stop(symbol, 29000., TriggerSignal::Mark, -1).unwrap()),
+        json!({"orderType": "stp", "symbol": "PF_XBTUSD", "side": "sell", "size": 1, "stopPrice": 29000.0, "triggerSignal": "mark"})
+    );
+    assert_eq!(
+        order_fields(&SendOrderRequest::stop_limit(symbol, 29000., 28900., TriggerSignal::Last, -1).unwrap()),
+        json!({"orderType": "stp", "symbol": "PF_XBTUSD", "side": "sell", "size": 1, "stopPrice": 29000.0, "limitPrice": 28900.0, "triggerSignal": "last"})
+    );
+    assert_eq!(
+        order_fields(&SendOrderRequest::take_profit(symbol, 32000., TriggerSignal::Index, -1).unwrap()),
+        json!({"orderType": "take_profit", "symbol": "PF_XBTUSD", "side": "sell", "size": 1, "stopPrice": 32000.0, "triggerSignal": "index"})
+    );
+    assert_eq!(
+        order_fields(&SendOrderRequest::take_profit_limit(symbol, 32000., 32100., TriggerSignal::Mark, -1).unwrap()),
+        json!({"orderType": "take_profit", "symbol": "PF_XBTUSD", "side": "sell", "size": 1, "stopPrice": 32000.0, "limitPrice": 32100.0, "triggerSignal": "mark"})
+    );
+    assert_eq!(
+        order_fields(&SendOrderRequest::trailing_stop(symbol, 1.5, TrailingStopDeviationUnit::Percent, TriggerSignal::Mark, -1).unwrap()),
+        json!({
+            "orderType": "trailing_stop",
+            "symbol": "PF_XBTUSD",
+            "side": "sell",
+            "size": 1,
+            "triggerSignal": "mark",
+            "trailingStopMaxDeviation": 1.5,
+            "trailingStopDeviationUnit": "PERCENT",
+        })
+    );
+}
+
+#[test]
+fn test_send_order_setters() {
+    let symbol: Symbol = "PF_XBTUSD".parse().unwrap();
+    let req = SendOrderRequest::market(symbol, 1).unwrap().cli_ord_id(Uuid::nil()).reduce_only(true);
+
+    let fields = order_fields(&req);
+    assert_eq!(fields["cliOrdId"], "00000000-0000-0000-0000-000000000000");
+    assert_eq!(fields["reduceOnly"], true);
+}
+
+#[test]
+fn test_send_order_rejects_zero_size() {
+    let symbol: Symbol = "PF_XBTUSD".parse().unwrap();
+
+    assert!(matches!(SendOrderRequest::market(symbol, 0), Err(KrakenError::InvalidOrder(_))));
+    assert!(matches!(SendOrderRequest::limit(symbol, 30000., 0), Err(KrakenError::InvalidOrder(_))));
+}
+
+const ORDER: &str = r#"{"orderId":"61ca5732-3478-42fe-8362-abbfd9465294","cliOrdId":null,"type":"lmt","symbol":"PF_XBTUSD","side":"buy","quantity":1,"filled":0,
+    "limitPrice":30000.0,"stopPrice":null,"reduceOnly":false,"timestamp":"2020-11-09T16:01:34.000Z","lastUpdateTimestamp":"2020-11-09T16:01:34.000Z"}"#;
+
+#[test]
+fn test_order_events() {
+    let event = |json: String| from_str::<OrderEvent>(&json).unwrap();
+
+    match event(format!(r#"{{"type":"PLACE","order":{},"reducedQuantity":null}}"#, ORDER)) {
+        OrderEvent::NewOrder { order, reduced_quantity } => {
+            assert_eq!(order.limit_price, 30000.);
+            assert_eq!(reduced_quantity, None);
+        }
+        e => panic!("unexpected {:?}", e),
+    }
+
+    match event(format!(r#"{{"type":"CANCEL","uid":"61ca5732-3478-42fe-8362-abbfd9465294","order":{}}}"#, ORDER)) {
+        OrderEvent::Cancel { uid, order } => assert_eq!(uid, order.order_id),
+        e => panic!("unexpected {:?}", e),
+    }
+
+    // Shaped like CANCEL apart from the reason, which must not be dropped
+    match event(format!(
+        r#"{{"type":"REJECT","uid":"61ca5732-3478-42fe-8362-abbfd9465294","order":{},"reason":"POST_WOULD_EXECUTE"}}"#,
+        ORDER
+    )) {
+        OrderEvent::Reject { reason, .. } => assert_eq!(reason, "POST_WOULD_EXECUTE"),
+        e => panic!("unexpected {:?}", e),
+    }
+
+    let execution = event(format!(
+        r#"{{"type":"EXECUTION","executionId":"e1ec9f63-2338-4c44-b40a-43486c6732d7","price":30000.0,"amount":1.0,"orderPriorEdit":null,"orderPriorExecution":{}}}"#,
+        ORDER
+    ));
+    assert_eq!(execution.order_id().unwrap().to_string(), "61ca5732-3478-42fe-8362-abbfd9465294");
+
+    let edited = ORDER.replace("30000.0", "30100.0");
+    match event(format!(r#"{{"type":"EDIT","old":{},"new":{},"reducedQuantity":null}}"#, ORDER, edited)) {
+        OrderEvent::Edit { old, new, .. } => assert_eq!((old.limit_price, new.limit_price), (30000., 30100.)),
+        e => panic!("unexpected {:?}", e),
+    }
+
+    // Unknown event types must not fail the whole response
+    let unknown = event(format!(r#"{{"type":"REPLACE","order":{}}}"#, ORDER));
+    assert!(matches!(unknown, OrderEvent::Other));
+    assert_eq!(unknown.order_id(), None);
+}
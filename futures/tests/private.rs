@@ -1,7 +1,10 @@
-use failure::Error;
+use anyhow::Error;
 use fehler::throws;
+use futures::{StreamExt, TryStreamExt};
 use kraken_futures::{
-    rest::{AccountsRequest, CancelAllOrdersRequest, CancelOrderRequest, KrakenRest, OpenPositionsRequest, SendOrderRequest},
+    rest::{AccountLogRequest, ExecutionEventsRequest, OrderEventsRequest, TriggerEventsRequest},
+    rest::{AccountsRequest, CancelAllOrdersRequest, CancelOrderRequest, KrakenRest, OpenOrdersRequest, OpenPositionsRequest, SendOrderRequest},
+    rest::{FeeScheduleVolumesRequest, FeeSchedulesRequest, InstrumentsRequest, LeveragePreferencesRequest, NotificationsRequest, PnlPreferencesRequest},
     Symbol,
 };
 use structopt::StructOpt;
@@ -46,6 +49,21 @@ fn test_open_positions() {
     rt.block_on(client.request(OpenPositionsRequest))?;
 }
 
+#[test]
+#[throws(Error)]
+fn test_open_orders() {
+    let _ = dotenv::dotenv();
+    let _ = env_logger::try_init();
+
+    let opt = Opt::from_args();
+
+    let mut rt = Runtime::new()?;
+
+    let client = KrakenRest::with_credential(None, &opt.kraken_api_key, &opt.kraken_api_secret);
+
+    rt.block_on(client.request_with_retry(OpenOrdersRequest))?;
+}
+
 #[test]
 #[throws(Error)]
 fn test_buy_and_cancel() {
@@ -58,11 +76,80 @@ fn test_buy_and_cancel() {
 
     let client = KrakenRest::with_credential(None, &opt.kraken_api_key, &opt.kraken_api_secret);
 
-    let resp = rt.block_on(client.request(SendOrderRequest::limit(Symbol::PerpetualInverse("XBTUSD".parse()?), 30000., -1)))?;
+    let resp = rt.block_on(client.request(SendOrderRequest::limit(Symbol::PerpetualInverse("XBTUSD".parse()?), 30000., -1)?))?;
 
     rt.block_on(client.request(CancelOrderRequest::from_order_id(resp.send_status.order_id().unwrap())))?;
 
-    rt.block_on(client.request(SendOrderRequest::limit(Symbol::PerpetualInverse("XBTUSD".parse()?), 30000., -1)))?;
+    rt.block_on(client.request(SendOrderRequest::limit(Symbol::PerpetualInverse("XBTUSD".parse()?), 30000., -1)?))?;
 
     rt.block_on(client.request(CancelAllOrdersRequest::all()))?;
 }
+
+#[test]
+#[throws(Error)]
+fn test_history() {
+    let _ = dotenv::dotenv();
+    let _ = env_logger::try_init();
+
+    let opt = Opt::from_args();
+
+    let mut rt = Runtime::new()?;
+
+    let client = KrakenRest::with_credential(None, &opt.kraken_api_key, &opt.kraken_api_secret);
+
+    rt.block_on(client.stream(OrderEventsRequest::default()).take(100).try_collect::<Vec<_>>())?;
+    rt.block_on(client.stream(TriggerEventsRequest::default()).take(100).try_collect::<Vec<_>>())?;
+    rt.block_on(client.stream(ExecutionEventsRequest::default()).take(100).try_collect::<Vec<_>>())?;
+    rt.block_on(client.stream(AccountLogRequest::default()).take(100).try_collect::<Vec<_>>())?;
+}
+
+#[test]
+#[throws(Error)]
+fn test_preferences() {
+    let _ = dotenv::dotenv();
+    let _ = env_logger::try_init();
+
+    let opt = Opt::from_args();
+
+    let mut rt = Runtime::new()?;
+
+    let client = KrakenRest::with_credential(None, &opt.kraken_api_key, &opt.kraken_api_secret);
+
+    rt.block_on(client.request(LeveragePreferencesRequest))?;
+    rt.block_on(client.request(PnlPreferencesRequest))?;
+}
+
+#[test]
+#[throws(Error)]
+fn test_fee_tier() {
+    let _ = dotenv::dotenv();
+    let _ = env_logger::try_init();
+
+    let opt = Opt::from_args();
+
+    let mut rt = Runtime::new()?;
+
+    let client = KrakenRest::with_credential(None, &opt.kraken_api_key, &opt.kraken_api_secret);
+
+    let schedules = rt.block_on(client.request(FeeSchedulesRequest))?;
+    let instruments = rt.block_on(client.request(InstrumentsRequest))?;
+    let volumes = rt.block_on(client.request(FeeScheduleVolumesRequest))?;
+
+    assert!(schedules.tier_for("PI_XBTUSD".parse()?, &instruments, &volumes).is_some());
+}
+
+#[test]
+#[throws(Error)]
+fn test_notifications() {
+    let _ = dotenv::dotenv();
+    let _ = env_logger::try_init();
+
+    let opt = Opt::from_args();
+
+    let mut rt = Runtime::new()?;
+
+    let client = KrakenRest::with_credential(None, &opt.kraken_api_key, &opt.kraken_api_secret);
+
+    let resp = rt.block_on(client.request(NotificationsRequest))?;
+    resp.touching("PI_XBTUSD".parse()?);
+}
@@ -1,6 +1,6 @@
-use failure::Error;
+use anyhow::Error;
 use fehler::throws;
-use kraken_futures::rest::{KrakenRest, OrderbookRequest, TickersRequest};
+use kraken_futures::rest::{FeeSchedulesRequest, InstrumentsRequest, KrakenRest, OrderbookRequest, TickersRequest};
 use tokio::runtime::Runtime;
 
 #[test]
@@ -27,3 +27,29 @@ fn test_tickers() {
 
     rt.block_on(client.request(TickersRequest))?;
 }
+
+#[test]
+#[throws(Error)]
+fn test_instruments() {
+    let _ = dotenv::dotenv();
+    let _ = env_logger::try_init();
+
+    let mut rt = Runtime::new()?;
+
+    let client = KrakenRest::new(None);
+
+    rt.block_on(client.request(InstrumentsRequest))?;
+}
+
+#[test]
+#[throws(Error)]
+fn test_fee_schedules() {
+    let _ = dotenv::dotenv();
+    let _ = env_logger::try_init();
+
+    let mut rt = Runtime::new()?;
+
+    let client = KrakenRest::new(None);
+
+    rt.block_on(client.request(FeeSchedulesRequest))?;
+}
@@ -0,0 +1,43 @@
+use anyhow::Error;
+use fehler::throws;
+use kraken_futures::errors::KrakenError;
+use kraken_futures::rest::{RateLimitMode, RateLimiter, RatePool};
+use std::time::{Duration, Instant};
+use tokio::runtime::Runtime;
+
+#[test]
+#[throws(Error)]
+fn test_reject_when_exhausted() {
+    let mut rt = Runtime::new()?;
+
+    let limiter = RateLimiter::new(RateLimitMode::Reject).with_budget(RatePool::Derivatives, 20, Duration::from_secs(60));
+    let shared = limiter.clone();
+
+    rt.block_on(limiter.acquire(RatePool::Derivatives, 10))?;
+    rt.block_on(shared.acquire(RatePool::Derivatives, 10))?;
+
+    match rt.block_on(limiter.acquire(RatePool::Derivatives, 10)) {
+        Err(KrakenError::RateLimited { pool, retry_after }) => {
+            assert_eq!(pool, RatePool::Derivatives);
+            assert!(retry_after > Duration::from_secs(25));
+        }
+        r => panic!("Expected RateLimited, got {:?}", r),
+    }
+
+    // The other pool has its own budget
+    rt.block_on(limiter.acquire(RatePool::History, 10))?;
+}
+
+#[test]
+#[throws(Error)]
+fn test_queue_until_refilled() {
+    let mut rt = Runtime::new()?;
+
+    let limiter = RateLimiter::new(RateLimitMode::Queue).with_budget(RatePool::Derivatives, 10, Duration::from_millis(200));
+
+    rt.block_on(limiter.acquire(RatePool::Derivatives, 10))?;
+
+    let start = Instant::now();
+    rt.block_on(limiter.acquire(RatePool::Derivatives, 5))?;
+    assert!(start.elapsed() >= Duration::from_millis(90));
+}
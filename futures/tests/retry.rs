@@ -0,0 +1,146 @@
+mod common;
+
+use common::FakeTransport;
+use http::StatusCode;
+use kraken_futures::errors::{ApiError, KrakenError};
+use kraken_futures::rest::{Idempotency, KrakenRest, RatePool, Request, RetryPolicy, SendOrderRequest, TickersRequest};
+use kraken_futures::TriggerSignal;
+use std::time::Duration;
+use uuid::Uuid;
+
+fn api_error(error: ApiError) -> KrakenError {
+    KrakenError::Api {
+        error,
+        message: String::new(),
+        status: StatusCode::OK,
+    }
+}
+
+#[test]
+fn test_default_retryable() {
+    assert!(RetryPolicy::default_retryable(&api_error(ApiError::NonceDuplicate)));
+    assert!(RetryPolicy::default_retryable(&api_error(ApiError::ApiLimitExceeded)));
+    assert!(!RetryPolicy::default_retryable(&api_error(ApiError::InvalidArgument)));
+    assert!(!RetryPolicy::default_retryable(&api_error(ApiError::AuthenticationError)));
+    assert!(!RetryPolicy::default_retryable(&KrakenError::NoApiKeySet));
+}
+
+#[test]
+fn test_exponential_backoff() {
+    let policy = RetryPolicy {
+        initial_backoff: Duration::from_millis(100),
+        max_backoff: Duration::from_secs(1),
+        jitter: false,
+        ..RetryPolicy::default()
+    };
+    let error = api_error(ApiError::Unavailable);
+
+    assert_eq!(policy.backoff(1, &error), Duration::from_millis(100));
+    assert_eq!(policy.backoff(2, &error), Duration::from_millis(200));
+    assert_eq!(policy.backoff(4, &error), Duration::from_millis(800));
+    assert_eq!(policy.backoff(10, &error), Duration::from_secs(1));
+
+    let limited = KrakenError::RateLimited {
+        pool: RatePool::Derivatives,
+        retry_after: Duration::from_secs(3),
+    };
+    assert_eq!(policy.backoff(1, &limited), Duration::from_secs(3));
+}
+
+#[test]
+fn test_jitter_stays_within_backoff() {
+    let policy = RetryPolicy::default();
+    let error = api_error(ApiError::Unavailable);
+
+    for _ in 0..100 {
+        let backoff = policy.backoff(3, &error);
+        assert!(backoff >= Duration::from_millis(200) && backoff <= Duration::from_millis(400));
+    }
+}
+
+// A gateway error page, after which the request may or may not have reached Kraken
+const BAD_GATEWAY: (StatusCode, &str) = (StatusCode::BAD_GATEWAY, "<html>502 Bad Gateway</html>");
+const TICKERS: (StatusCode, &str) = (StatusCode::OK, r#"{"result":"success","tickers":[]}"#);
+const ORDER_PLACED: (StatusCode, &str) = (
+    StatusCode::OK,
+    r#"{"result":"success","sendStatus":{"order_id":"61ca5732-3478-42fe-8362-abbfd9465294","status":"placed","receivedTime":"2020-11-09T16:01:34.000Z","orderEvents":[]}}"#,
+);
+
+const CLI_ORD_ID: &str = "2f1ac0a6-9a0b-4fa1-9d8e-d1b7b0c3e5f4";
+
+fn client(transport: &FakeTransport) -> KrakenRest {
+    let policy = RetryPolicy {
+        initial_backoff: Duration::from_millis(0),
+        jitter: false,
+        ..RetryPolicy::default()
+    };
+    KrakenRest::with_credential(None, "key", "c2VjcmV0")
+        .with_transport(transport.clone())
+        .with_retry_policy(policy)
+}
+
+fn paths(transport: &FakeTransport) -> Vec<String> {
+    transport
+        .sent()
+        .iter()
+        .map(|r| r.url.path().trim_start_matches("/derivatives/api/v3").to_string())
+        .collect()
+}
+
+#[test]
+fn test_send_order_idempotency() {
+    let symbol = "PF_XBTUSD".parse().unwrap();
+    let cli_ord_id: Uuid = CLI_ORD_ID.parse().unwrap();
+
+    // Without a cli_ord_id no order type can be told apart from an unrelated one
+    for req in [
+        SendOrderRequest::limit(symbol, 30000., 1).unwrap(),
+        SendOrderRequest::post_only(symbol, 30000., 1).unwrap(),
+        SendOrderRequest::market(symbol, 1).unwrap(),
+        SendOrderRequest::stop(symbol, 29000., TriggerSignal::Mark, 1).unwrap(),
+    ] {
+        assert_eq!(req.idempotency(), Idempotency::Unsafe);
+        assert_eq!(req.cli_ord_id(cli_ord_id).idempotency(), Idempotency::Idempotent);
+    }
+}
+
+#[tokio::test]
+async fn test_idempotent_is_resent() {
+    let transport = FakeTransport::script(vec![BAD_GATEWAY, TICKERS]);
+
+    client(&transport).request_with_retry(TickersRequest).await.unwrap();
+    assert_eq!(paths(&transport), vec!["/tickers", "/tickers"]);
+}
+
+#[tokio::test]
+async fn test_idempotent_order_is_resent() {
+    let transport = FakeTransport::script(vec![BAD_GATEWAY, ORDER_PLACED]);
+    let req = SendOrderRequest::limit("PF_XBTUSD".parse().unwrap(), 30000., 1)
+        .unwrap()
+        .cli_ord_id(CLI_ORD_ID.parse().unwrap());
+
+    client(&transport).request_with_retry(req).await.unwrap();
+    assert_eq!(paths(&transport), vec!["/sendorder", "/sendorder"]);
+}
+
+#[tokio::test]
+async fn test_unsafe_is_not_resent() {
+    let transport = FakeTransport::script(vec![BAD_GATEWAY, ORDER_PLACED]);
+    let req = SendOrderRequest::limit("PF_XBTUSD".parse().unwrap(), 30000., 1).unwrap();
+
+    match client(&transport).request_with_retry(req).await {
+        Err(KrakenError::CannotDeserializeResponse { status, .. }) => assert_eq!(status, StatusCode::BAD_GATEWAY),
+        r => panic!("unexpected {:?}", r.map(|_| ())),
+    }
+    assert_eq!(paths(&transport), vec!["/sendorder"]);
+}
+
+#[tokio::test]
+async fn test_unsafe_is_resent_after_kraken_error() {
+    // Kraken answered, so the order was not executed
+    let transport = FakeTransport::script(vec![(StatusCode::OK, r#"{"result":"error","error":"apiLimitExceeded"}"#), ORDER_PLACED]);
+    let req = SendOrderRequest::limit("PF_XBTUSD".parse().unwrap(), 30000., 1).unwrap();
+
+    client(&transport).request_with_retry(req).await.unwrap();
+    assert_eq!(paths(&transport), vec!["/sendorder", "/sendorder"]);
+}
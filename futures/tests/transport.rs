@@ -0,0 +1,129 @@
+mod common;
+
+use chrono::{DateTime, Utc};
+use common::{header, FakeTransport};
+use http::{Method, StatusCode};
+use kraken_futures::errors::{ApiError, KrakenError};
+use kraken_futures::rest::{CancelOrderRequest, HttpRequest, HttpResponse, KrakenRest, Middleware, MonotonicNonce, NonceSource, ServerClock, Status, TickersRequest};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn test_public_request() {
+    let transport = FakeTransport::new(
+        StatusCode::OK,
+        r#"{"result":"success","serverTime":"2020-08-27T17:03:33.196Z","tickers":[{"symbol":"pi_xbtusd","last":11318.5,"lastTime":"2020-08-27T17:03:30.000Z"}]}"#,
+    );
+    let client = KrakenRest::new(None).with_transport(transport.clone());
+
+    let resp = client.request(TickersRequest).await.unwrap();
+    assert_eq!(resp.tickers.len(), 1);
+    assert_eq!(resp.tickers[0].last, 11318.5);
+
+    let sent = transport.sent();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].method, Method::GET);
+    assert_eq!(sent[0].url.as_str(), "https://futures.kraken.com/derivatives/api/v3/tickers");
+    assert_eq!(header(&sent[0], "APIKey"), None);
+}
+
+#[tokio::test]
+async fn test_url_with_api_path() {
+    let transport = FakeTransport::new(StatusCode::OK, r#"{"result":"success","tickers":[]}"#);
+    let client = KrakenRest::new("https://demo-futures.kraken.com/derivatives/api/v3/").with_transport(transport.clone());
+
+    client.request(TickersRequest).await.unwrap();
+    assert_eq!(transport.sent()[0].url.as_str(), "https://demo-futures.kraken.com/derivatives/api/v3/tickers");
+}
+
+#[tokio::test]
+async fn test_signed_request() {
+    let transport = FakeTransport::new(StatusCode::OK, r#"{"result":"success","cancelStatus":{"status":"notFound"}}"#);
+    let client = KrakenRest::with_credential(None, "key", "c2VjcmV0").with_transport(transport.clone());
+
+    let resp = client.request(CancelOrderRequest::from_order_id(Uuid::nil())).await.unwrap();
+    assert_eq!(resp.cancel_status.status, Status::NotFound);
+
+    let sent = transport.sent();
+    assert_eq!(sent[0].method, Method::POST);
+    assert!(sent[0].body.contains("order_id=00000000-0000-0000-0000-000000000000"));
+    assert_eq!(header(&sent[0], "APIKey"), Some("key"));
+    assert!(header(&sent[0], "Authent").is_some());
+    assert!(sent[0].body.contains(&format!("nonce={}", header(&sent[0], "Nonce").unwrap())));
+}
+
+#[tokio::test]
+async fn test_error_response() {
+    let transport = FakeTransport::new(StatusCode::OK, r#"{"result":"error","error":"apiLimitExceeded"}"#);
+    let client = KrakenRest::new(None).with_transport(transport);
+
+    match client.request(TickersRequest).await {
+        Err(KrakenError::Api { error, status, .. }) => {
+            assert_eq!(error, ApiError::ApiLimitExceeded);
+            assert_eq!(status, StatusCode::OK);
+        }
+        r => panic!("unexpected {:?}", r.map(|_| ())),
+    }
+}
+
+#[derive(Clone, Default)]
+struct Recorder {
+    events: Arc<Mutex<Vec<String>>>,
+}
+
+impl Middleware<KrakenError> for Recorder {
+    fn on_request(&self, endpoint: &str, req: &HttpRequest) {
+        let key = header(req, "APIKey").unwrap_or_default();
+        self.events.lock().unwrap().push(format!("request {} {}", endpoint, key));
+    }
+
+    fn on_response(&self, endpoint: &str, req: &HttpRequest, resp: Result<&HttpResponse, &KrakenError>, _latency: Duration) {
+        let resp = resp.unwrap();
+        let signature = header(req, "Authent").unwrap_or_default();
+        self.events
+            .lock()
+            .unwrap()
+            .push(format!("response {} {} {} {}", endpoint, signature, resp.status.as_u16(), resp.body));
+    }
+}
+
+#[tokio::test]
+async fn test_middleware() {
+    let transport = FakeTransport::new(StatusCode::BAD_REQUEST, r#"{"result":"error","error":"invalidArgument: order_id"}"#);
+    let recorder = Recorder::default();
+    let client = KrakenRest::with_credential(None, "key", "c2VjcmV0")
+        .with_transport(transport.clone())
+        .with_middleware(recorder.clone());
+
+    assert!(client.request(CancelOrderRequest::from_order_id(Uuid::nil())).await.is_err());
+
+    assert_eq!(
+        *recorder.events.lock().unwrap(),
+        vec![
+            "request /cancelorder <redacted>".to_string(),
+            r#"response /cancelorder <redacted> 400 {"result":"error","error":"invalidArgument: order_id"}"#.to_string(),
+        ]
+    );
+    // Only the copy handed to middlewares is redacted
+    assert_eq!(header(&transport.sent()[0], "APIKey"), Some("key"));
+}
+
+#[tokio::test]
+async fn test_server_clock() {
+    let transport = FakeTransport::new(StatusCode::OK, r#"{"result":"success","serverTime":"2100-01-01T00:00:00.000Z","tickers":[]}"#);
+    let clock = ServerClock::new();
+    let client = KrakenRest::new(None)
+        .with_transport(transport)
+        .with_server_clock(clock.clone())
+        .with_nonce_source(MonotonicNonce::new().with_clock(clock.clone()));
+
+    client.request(TickersRequest).await.unwrap();
+
+    let server: DateTime<Utc> = "2100-01-01T00:00:00Z".parse().unwrap();
+    assert!((clock.now() - server).num_seconds().abs() < 5);
+    assert!((client.server_clock().offset() - clock.offset()).is_zero());
+
+    let nonce = MonotonicNonce::new().with_clock(clock).next_nonce() as i64;
+    assert!((nonce - server.timestamp_nanos()).abs() < 5_000_000_000);
+}
@@ -0,0 +1,90 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
+// Assets listed before Kraken dropped the X/Z prefixes still use them in balances and pair names
+const LEGACY_NAMES: [(&str, &str); 17] = [
+    ("XETC", "ETC"),
+    ("XETH", "ETH"),
+    ("XLTC", "LTC"),
+    ("XMLN", "MLN"),
+    ("XREP", "REP"),
+    ("XXBT", "XBT"),
+    ("XXDG", "XDG"),
+    ("XXLM", "XLM"),
+    ("XXMR", "XMR"),
+    ("XXRP", "XRP"),
+    ("XZEC", "ZEC"),
+    ("ZAUD", "AUD"),
+    ("ZCAD", "CAD"),
+    ("ZEUR", "EUR"),
+    ("ZGBP", "GBP"),
+    ("ZJPY", "JPY"),
+    ("ZUSD", "USD"),
+];
+
+// A spot asset under its alternative name, so "XXBT" and "XBT" are the same currency.
+// Staked and earn balances keep their suffix, e.g. "DOT.S" or "XBT.M".
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Currency(String);
+
+impl Currency {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    // The currency without the balance suffix, "DOT" for "DOT.S"
+    pub fn base(&self) -> Currency {
+        match self.0.find('.') {
+            Some(i) => Currency(self.0[..i].to_string()),
+            None => self.clone(),
+        }
+    }
+}
+
+impl FromStr for Currency {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Infallible> {
+        let s = s.to_uppercase();
+        let (name, suffix) = match s.find('.') {
+            Some(i) => s.split_at(i),
+            None => (s.as_str(), ""),
+        };
+        let name = LEGACY_NAMES.iter().find(|(legacy, _)| *legacy == name).map_or(name, |(_, alt)| alt);
+
+        Ok(Currency(format!("{}{}", name, suffix)))
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl fmt::Debug for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Serialize for Currency {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Currency {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Ok(s.parse().unwrap())
+    }
+}
@@ -0,0 +1,56 @@
+use serde::{de::Error as DeError, Deserialize, Deserializer};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// Kraken encodes decimals as strings so they keep their precision
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StringOrF64 {
+    String(String),
+    F64(f64),
+}
+
+pub fn string_or_f64<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match StringOrF64::deserialize(deserializer)? {
+        StringOrF64::String(s) => s.parse().map_err(DeError::custom),
+        StringOrF64::F64(f) => Ok(f),
+    }
+}
+
+pub fn option_string_or_f64<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<StringOrF64>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(StringOrF64::String(s)) if s.is_empty() => Ok(None),
+        Some(StringOrF64::String(s)) => s.parse().map(Some).map_err(DeError::custom),
+        Some(StringOrF64::F64(f)) => Ok(Some(f)),
+    }
+}
+
+pub fn map_string_or_f64<'de, D, K>(deserializer: D) -> Result<HashMap<K, f64>, D::Error>
+where
+    D: Deserializer<'de>,
+    K: Deserialize<'de> + Eq + Hash,
+{
+    HashMap::<K, StringOrF64>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(k, v)| match v {
+            StringOrF64::String(s) => s.parse().map(|f| (k, f)).map_err(DeError::custom),
+            StringOrF64::F64(f) => Ok((k, f)),
+        })
+        .collect()
+}
+
+// Kraken lists flags as a comma separated string
+pub fn comma_list<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = Option::<String>::deserialize(deserializer)?.unwrap_or_default();
+    Ok(s.split(',').filter(|s| !s.is_empty()).map(|s| s.to_string()).collect())
+}
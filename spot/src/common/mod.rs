@@ -0,0 +1,8 @@
+mod currency;
+pub(crate) mod de;
+mod order;
+mod side;
+
+pub use currency::Currency;
+pub use order::{OrderFlag, OrderStatus, OrderTime, OrderType, Price, PriceOffset, TimeInForce, Trigger};
+pub use side::Side;
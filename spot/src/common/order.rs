@@ -0,0 +1,180 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize, Serializer};
+use std::fmt;
+use std::time::Duration;
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum OrderType {
+    Market,
+    Limit,
+    StopLoss,
+    TakeProfit,
+    StopLossLimit,
+    TakeProfitLimit,
+    TrailingStop,
+    TrailingStopLimit,
+    SettlePosition,
+    // Can only be placed from Kraken's website, but shows up in order queries
+    Iceberg,
+    // Types Kraken added after this list was written, only seen in responses
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum OrderStatus {
+    Pending,
+    Open,
+    Closed,
+    Canceled,
+    Expired,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum OrderFlag {
+    // Post only, rejected instead of taking liquidity
+    Post,
+    // Pay the fee in the base currency
+    Fcib,
+    // Pay the fee in the quote currency
+    Fciq,
+    // Disable market price protection for market orders
+    Nompp,
+    // Volume in the quote currency
+    Viqc,
+}
+
+impl fmt::Display for OrderFlag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let flag = match self {
+            OrderFlag::Post => "post",
+            OrderFlag::Fcib => "fcib",
+            OrderFlag::Fciq => "fciq",
+            OrderFlag::Nompp => "nompp",
+            OrderFlag::Viqc => "viqc",
+        };
+        write!(f, "{}", flag)
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeInForce {
+    GTC,
+    IOC,
+    // Good till `expire_time`
+    GTD,
+}
+
+// The price that triggers stop loss, take profit and trailing stop orders
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Trigger {
+    Index,
+    Last,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceOffset {
+    // "+", above the last traded price
+    Plus,
+    // "-", below the last traded price
+    Minus,
+    // "#", above or below depending on the side and order type
+    Auto,
+}
+
+// An order price, either fixed or relative to the last traded price, e.g. `Price::minus(2.).percent()` is "-2%"
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Price {
+    value: f64,
+    offset: Option<PriceOffset>,
+    percent: bool,
+}
+
+impl Price {
+    pub fn fixed(value: f64) -> Self {
+        Price {
+            value,
+            offset: None,
+            percent: false,
+        }
+    }
+
+    pub fn plus(value: f64) -> Self {
+        Price {
+            offset: Some(PriceOffset::Plus),
+            ..Self::fixed(value)
+        }
+    }
+
+    pub fn minus(value: f64) -> Self {
+        Price {
+            offset: Some(PriceOffset::Minus),
+            ..Self::fixed(value)
+        }
+    }
+
+    pub fn auto(value: f64) -> Self {
+        Price {
+            offset: Some(PriceOffset::Auto),
+            ..Self::fixed(value)
+        }
+    }
+
+    // Reads the value as a percentage of the last traded price
+    pub fn percent(self) -> Self {
+        Price { percent: true, ..self }
+    }
+}
+
+impl From<f64> for Price {
+    fn from(value: f64) -> Self {
+        Price::fixed(value)
+    }
+}
+
+impl fmt::Display for Price {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let prefix = match self.offset {
+            None => "",
+            Some(PriceOffset::Plus) => "+",
+            Some(PriceOffset::Minus) => "-",
+            Some(PriceOffset::Auto) => "#",
+        };
+        let suffix = if self.percent { "%" } else { "" };
+        write!(f, "{}{}{}", prefix, self.value, suffix)
+    }
+}
+
+impl Serialize for Price {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+// When an order becomes active or expires
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderTime {
+    Now,
+    After(Duration),
+    At(DateTime<Utc>),
+}
+
+impl Serialize for OrderTime {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            OrderTime::Now => serializer.serialize_str("0"),
+            OrderTime::After(d) => serializer.serialize_str(&format!("+{}", d.as_secs())),
+            OrderTime::At(t) => serializer.serialize_str(&t.timestamp().to_string()),
+        }
+    }
+}
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Side {
+    // Trades abbreviate the side
+    #[serde(alias = "b")]
+    Buy,
+    #[serde(alias = "s")]
+    Sell,
+}
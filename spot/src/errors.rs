@@ -1,27 +1,42 @@
-use failure::Fail;
+use http::StatusCode;
 use serde::Deserialize;
-use serde_json::Value;
+use thiserror::Error;
 
-#[derive(Fail, Debug)]
+#[derive(Error, Debug)]
 pub enum KrakenError {
-    #[fail(display = "No Api key set for private api")]
+    #[error("No Api key set for private api")]
     NoApiKeySet,
-    #[fail(display = "Kraken error {:?}", _0)]
-    KrakenError(Vec<Value>),
-    #[fail(display = "Cannot deserialize response {:?}", _0)]
-    CannotDeserializeResponse(String),
-}
-
-impl From<KrakenErrorResponse> for KrakenError {
-    fn from(error: KrakenErrorResponse) -> KrakenError {
-        KrakenError::KrakenError(error.error)
-    }
+    #[error("Kraken error ({status}) {errors:?}")]
+    Api { errors: Vec<String>, status: StatusCode },
+    #[error("Transport error {0}")]
+    Transport(#[from] reqwest::Error),
+    #[error("Cannot deserialize response ({status}) {body}")]
+    CannotDeserializeResponse {
+        status: StatusCode,
+        body: String,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("Json error {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Cannot encode request {0}")]
+    UrlEncode(#[from] serde_urlencoded::ser::Error),
+    #[error("Invalid url {0}")]
+    Url(#[from] url::ParseError),
+    #[error("Api secret is not valid base64 {0}")]
+    InvalidApiSecret(#[from] base64::DecodeError),
+    #[error("Invalid order batch: {0}")]
+    InvalidOrderBatch(String),
+    #[error("Invalid id list: {0}")]
+    InvalidIdList(String),
+    #[error("Invalid heartbeat: {0}")]
+    InvalidHeartbeat(String),
 }
 
 // The error response from bitmex;
 #[derive(Deserialize, Debug, Clone)]
 pub(crate) struct KrakenErrorResponse {
-    pub(crate) error: Vec<Value>,
+    pub(crate) error: Vec<String>,
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -1,5 +1,8 @@
 #![allow(unused)]
 
+mod common;
 pub mod errors;
 pub mod rest;
 pub mod ws;
+
+pub use common::*;
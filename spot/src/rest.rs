@@ -1,5 +1,24 @@
 mod client;
 mod models;
+mod nonce;
+mod registry;
 
 pub use client::Kraken;
-pub use models::{GetServerTimeRequest, GetServerTimeResponse, GetTradeBalanceRequest, GetTradeBalanceResponse};
+pub use kraken_core::ServerClock;
+pub use kraken_core::{HttpRequest, HttpResponse, Middleware, Paginated, Transport};
+pub use models::Request;
+pub use models::{AddOrderBatchRequest, AddOrderBatchResponse, AddOrderRequest, AddOrderResponse, BatchOrderResult, OrderDescription};
+pub use models::{Asset, AssetPair, AssetPairsRequest, AssetPairsResponse, AssetsRequest, AssetsResponse, FeeTier};
+pub use models::{BalanceExRequest, BalanceExResponse, BalanceRequest, BalanceResponse, ExtendedBalance};
+pub use models::{CancelAllOrdersAfterRequest, CancelAllOrdersAfterResponse, CancelAllRequest, CancelAllResponse, CancelOrderRequest, CancelOrderResponse};
+pub use models::{Candle, OhlcRequest, OhlcResponse, Spread, SpreadRequest, SpreadResponse};
+pub use models::{CloseTime, ClosedOrdersRequest, ClosedOrdersResponse, OpenOrdersRequest, OpenOrdersResponse, Order, OrderDetails, QueryOrdersRequest, QueryOrdersResponse};
+pub use models::{Depth, DepthLevel, DepthRequest, DepthResponse, Trade, TradeOrderType, TradesRequest, TradesResponse};
+pub use models::{EditOrderRequest, EditOrderResponse, EditStatus};
+pub use models::{GetServerTimeRequest, GetServerTimeResponse, SystemStatus, SystemStatusRequest, SystemStatusResponse};
+pub use models::{GetTradeBalanceRequest, GetTradeBalanceResponse, PairFee, TradeVolumeRequest, TradeVolumeResponse};
+pub use models::{LastTrade, Ticker, TickerLevel, TickerRequest, TickerResponse, TradeCount, Window};
+pub use models::{LedgerEntry, LedgerType, LedgersRequest, LedgersResponse, QueryLedgersRequest, QueryLedgersResponse};
+pub use models::{OwnTrade, QueryTradesRequest, QueryTradesResponse, TradeFilter, TradesHistoryRequest, TradesHistoryResponse};
+pub use nonce::{MonotonicNonce, NonceSource, PersistentNonce};
+pub use registry::Registry;
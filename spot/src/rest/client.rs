@@ -1,38 +1,49 @@
-use super::models::Request;
+use super::models::{CancelAllOrdersAfterRequest, GetServerTimeRequest, Request};
+use super::nonce::{MonotonicNonce, NonceSource};
 use crate::errors::{KrakenError, KrakenErrorResponse, KrakenResponse};
 use base64::{decode as b64decode, encode as b64encode};
-use chrono::Utc;
+use chrono::{Duration, Utc};
 use derive_builder::Builder;
 use fehler::{throw, throws};
+use futures::stream::Stream;
+use futures::Future;
 use http::Method;
-use log::error;
-use reqwest::{Client, Response};
+use kraken_core::ServerClock;
+use kraken_core::{paginate, HttpRequest, HttpResponse, Middleware, Paginated, Transport};
+use log::{debug, error, warn};
+use reqwest::Client;
 use ring::digest::{digest, SHA256};
 use ring::hmac;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use serde_json::{from_str, to_string as to_jstring, to_value};
 use serde_urlencoded::to_string as to_ustring;
-use std::ascii::escape_default;
 use std::str;
+use std::sync::Arc;
+use std::time::{Duration as StdDuration, Instant};
+use tokio::time::delay_for;
 use url::Url;
 
-fn show(bs: &[u8]) -> String {
-    let mut visible = String::new();
-    for &b in bs {
-        let part: Vec<u8> = escape_default(b).collect();
-        visible.push_str(str::from_utf8(&part).unwrap());
-    }
-    visible
-}
-
 const REST_URL: &'static str = "https://api.kraken.com";
+// Redacted from the requests handed to middlewares
+const CREDENTIAL_HEADERS: [&str; 2] = ["API-Key", "API-Sign"];
 
 #[derive(Clone, Builder)]
 pub struct Kraken {
-    client: Client,
+    // Any `Transport`, e.g. a `reqwest::Client` configured with timeouts or a proxy
+    #[builder(default = "Arc::new(Client::new())")]
+    transport: Arc<dyn Transport<KrakenError>>,
     #[builder(default)]
     credential: Option<(String, String)>,
+    // Clones share the nonce source. Set the same `Arc` on separately built clients to share one between them.
+    #[builder(default = "Arc::new(MonotonicNonce::new())")]
+    nonce: Arc<dyn NonceSource>,
+    // Called in order around every request
+    #[builder(default)]
+    middlewares: Vec<Arc<dyn Middleware<KrakenError>>>,
+    // Pass a clone to `MonotonicNonce::with_clock` to sign with server time
+    #[builder(default)]
+    clock: ServerClock,
 }
 
 impl Default for Kraken {
@@ -44,15 +55,21 @@ impl Default for Kraken {
 impl Kraken {
     pub fn new() -> Self {
         Kraken {
-            client: Client::new(),
+            transport: Arc::new(Client::new()),
             credential: None,
+            nonce: Arc::new(MonotonicNonce::new()),
+            middlewares: vec![],
+            clock: ServerClock::new(),
         }
     }
 
     pub fn with_credential(api_key: &str, api_secret: &str) -> Self {
         Kraken {
-            client: Client::new(),
+            transport: Arc::new(Client::new()),
             credential: Some((api_key.into(), api_secret.into())),
+            nonce: Arc::new(MonotonicNonce::new()),
+            middlewares: vec![],
+            clock: ServerClock::new(),
         }
     }
 
@@ -60,7 +77,45 @@ impl Kraken {
         KrakenBuilder::default()
     }
 
-    #[throws(failure::Error)]
+    // The offset measured by the last `sync_time`
+    pub fn server_clock(&self) -> &ServerClock {
+        &self.clock
+    }
+
+    // Measures the offset to Kraken's clock. Spot responses don't carry the server time, so call this
+    // periodically if the local clock drifts.
+    #[throws(KrakenError)]
+    pub async fn sync_time(&self) -> Duration {
+        let started = Instant::now();
+        let resp = self.request(GetServerTimeRequest).await?;
+        // The server time is truncated to the second, half a second is the expected error
+        self.clock.observe(resp.rfc1123 + Duration::milliseconds(500), started.elapsed());
+        self.clock.offset()
+    }
+
+    // Re-arms the dead man's switch with `timeout` every `interval` for as long as the returned future is
+    // polled, so orders are cancelled if the process stops. Keep `interval` well below `timeout` so that a
+    // failed request, which is only logged, can be retried in time. Disarm with `CancelAllOrdersAfterRequest::disarm`.
+    // Fails if `interval` is not below `timeout`, as the switch would trigger between two calls.
+    #[throws(KrakenError)]
+    pub fn heartbeat(&self, timeout: StdDuration, interval: StdDuration) -> impl Future<Output = ()> + Send + 'static {
+        if interval >= timeout {
+            throw!(KrakenError::InvalidHeartbeat(format!("interval {:?} is not below timeout {:?}", interval, timeout)));
+        }
+        let client = self.clone();
+
+        async move {
+            loop {
+                match client.request(CancelAllOrdersAfterRequest::new(timeout)).await {
+                    Ok(resp) => debug!("Dead man's switch triggers at {:?}", resp.trigger_time),
+                    Err(e) => warn!("Cannot re-arm the dead man's switch: {}", e),
+                }
+                delay_for(interval).await;
+            }
+        }
+    }
+
+    #[throws(KrakenError)]
     pub async fn request<R>(&self, req: R) -> R::Response
     where
         R: Request,
@@ -69,35 +124,73 @@ impl Kraken {
         let url = format!("{}{}", &*REST_URL, R::ENDPOINT);
         let url = Url::parse(&url)?;
 
-        let nonce = Utc::now().timestamp_millis();
+        let nonce = self.nonce.next_nonce();
 
         let mut body = "".to_string();
+        let mut content_type = "application/x-www-form-urlencoded";
 
-        if R::HAS_PAYLOAD {
+        if R::HAS_PAYLOAD && R::JSON_BODY {
+            let mut payload = to_value(&req)?;
+            if let (true, Some(payload)) = (R::SIGNED, payload.as_object_mut()) {
+                payload.insert("nonce".to_string(), nonce.into());
+            }
+            body = to_jstring(&payload)?;
+            content_type = "application/json";
+        } else if R::HAS_PAYLOAD {
             let mut uqs = req.to_url_query();
 
-            uqs.push(("nonce".to_string(), nonce.to_string()));
+            // Public endpoints take no nonce
+            if R::SIGNED {
+                uqs.push(("nonce".to_string(), nonce.to_string()));
+            }
             body = to_ustring(&uqs)?;
         }
 
-        let mut builder = self.client.request(Method::POST, url.clone());
+        let mut headers = vec![("User-Agent".to_string(), "kraken-rs".to_string()), ("Content-Type".to_string(), content_type.to_string())];
 
         if R::SIGNED {
             let (key, signature) = self.signature(&url, &body, nonce)?;
-            builder = builder.header("API-Key", key).header("API-Sign", signature);
+            headers.push(("API-Key".to_string(), key.to_string()));
+            headers.push(("API-Sign".to_string(), signature));
         }
 
-        let resp = builder
-            .header("User-Agent", "kraken-rs")
-            .header("Content-Type", "application/x-www-form-urlencoded")
-            .body(body)
-            .send()
-            .await?;
+        let req = HttpRequest {
+            method: Method::POST,
+            url,
+            headers,
+            body,
+        };
+
+        let logged = req.redacted(&CREDENTIAL_HEADERS);
+        for middleware in &self.middlewares {
+            middleware.on_request(R::ENDPOINT, &logged);
+        }
 
-        self.handle_response(resp).await?
+        let started = Instant::now();
+        let resp = self.transport.send(req).await;
+        let latency = started.elapsed();
+        for middleware in &self.middlewares {
+            middleware.on_response(R::ENDPOINT, &logged, resp.as_ref(), latency);
+        }
+
+        self.handle_response(resp?)?
     }
 
-    #[throws(failure::Error)]
+    // Requests every page of `req` in turn, following the offset or cursor of each response.
+    pub fn stream<R>(&self, req: R) -> impl Stream<Item = Result<R::Item, KrakenError>>
+    where
+        R: Request + Paginated<Page = <R as Request>::Response>,
+        R::Response: DeserializeOwned,
+    {
+        let client = self.clone();
+
+        paginate(req, move |req| {
+            let client = client.clone();
+            async move { client.request(req).await }
+        })
+    }
+
+    #[throws(KrakenError)]
     fn check_key(&self) -> (&str, &str) {
         match self.credential.as_ref() {
             None => throw!(KrakenError::NoApiKeySet),
@@ -105,41 +198,41 @@ impl Kraken {
         }
     }
 
-    #[throws(failure::Error)]
-    pub(crate) fn signature(&self, url: &Url, body: &str, nonce: i64) -> (&str, String) {
-        println!("input to signature: {}, {}", url.path(), body);
+    #[throws(KrakenError)]
+    pub(crate) fn signature(&self, url: &Url, body: &str, nonce: u64) -> (&str, String) {
         let (key, secret) = self.check_key()?;
+        // Never log the key, the secret or the signature
+        debug!("Signing request to {} with nonce {}", url.path(), nonce);
+
         // Signature: Message signature using HMAC-SHA512 of (URI path + SHA256(nonce + POST data)) and base64 decoded secret API key
         let encoded = format!("{}{}", nonce, body);
-        println!("encoded {}", encoded);
 
         let mut message = url.path().as_bytes().to_owned();
         message.extend(digest(&SHA256, encoded.as_bytes()).as_ref());
 
-        println!("message {}", show(&message));
-
         let signed_key = hmac::Key::new(hmac::HMAC_SHA512, &b64decode(secret)?);
         let signature = hmac::sign(&signed_key, &message);
         let signature = b64encode(&signature);
 
-        println!("Sig2 {}", signature);
-
         (key, signature)
     }
 
-    #[throws(failure::Error)]
-    async fn handle_response<T: DeserializeOwned>(&self, resp: Response) -> T {
-        let resp = resp.text().await?;
-        if let Ok(p) = from_str::<KrakenResponse<T>>(&resp) {
-            return p.result;
-        }
-
-        if let Ok(p) = from_str::<KrakenErrorResponse>(&resp) {
-            throw!(KrakenError::from(p))
+    #[throws(KrakenError)]
+    fn handle_response<T: DeserializeOwned>(&self, resp: HttpResponse) -> T {
+        let HttpResponse { status, body } = resp;
+        let source = match from_str::<KrakenResponse<T>>(&body) {
+            Ok(p) => return p.result,
+            Err(e) => e,
+        };
+
+        if let Ok(p) = from_str::<KrakenErrorResponse>(&body) {
+            if !p.error.is_empty() {
+                throw!(KrakenError::Api { errors: p.error, status })
+            }
         }
 
-        error!("Cannot deserialize {}", resp);
-        throw!(KrakenError::CannotDeserializeResponse(resp));
+        error!("Cannot deserialize {}", body);
+        throw!(KrakenError::CannotDeserializeResponse { status, body, source });
     }
 }
 
@@ -151,8 +244,12 @@ trait ToUrlQuery: Serialize {
 
     fn to_url_query(&self) -> Vec<(String, String)> {
         let v = to_value(self).unwrap();
-        let v = v.as_object().unwrap();
         let mut vec = vec![];
+        // Unit structs carry no parameters, but private requests still need the nonce
+        let v = match v.as_object() {
+            Some(v) => v,
+            None => return vec,
+        };
 
         for (key, value) in v.into_iter() {
             if value.is_null() {
@@ -171,3 +268,21 @@ trait ToUrlQuery: Serialize {
 }
 
 impl<S: Serialize> ToUrlQuery for S {}
+
+#[cfg(test)]
+mod tests {
+    use super::Kraken;
+    use url::Url;
+
+    // The example from https://docs.kraken.com/rest/#section/Authentication/Headers-and-Signature
+    #[test]
+    fn test_signature() {
+        let client = Kraken::with_credential("key", "kQH5HW/8p1uGOVjbgWA7FunAmGO8lsSUXNsu3eow76sz84Q18fWxnyRzBHCd3pd5nE9qa99HAZtuZuj6F1huXg==");
+        let url = Url::parse("https://api.kraken.com/0/private/AddOrder").unwrap();
+        let body = "nonce=1616492376594&ordertype=limit&pair=XBTUSD&price=37500&type=buy&volume=1.25";
+
+        let (key, signature) = client.signature(&url, body, 1616492376594).unwrap();
+        assert_eq!(key, "key");
+        assert_eq!(signature, "4/dpxb3iT4tp/ZCVEwSnEsLxx0bqyhLpdfOpc6fn7OR8+UClSV5n9E6aSS8MPtnRfp32bAb0nmbRn6H8ndwLUQ==");
+    }
+}
@@ -1,8 +1,19 @@
 mod private;
 mod public;
 
-pub use private::{GetTradeBalanceRequest, GetTradeBalanceResponse};
-pub use public::{GetServerTimeRequest, GetServerTimeResponse};
+pub use private::{AddOrderBatchRequest, AddOrderBatchResponse, AddOrderRequest, AddOrderResponse, BatchOrderResult, OrderDescription};
+pub use private::{BalanceExRequest, BalanceExResponse, BalanceRequest, BalanceResponse, ExtendedBalance};
+pub use private::{CancelAllOrdersAfterRequest, CancelAllOrdersAfterResponse, CancelAllRequest, CancelAllResponse, CancelOrderRequest, CancelOrderResponse};
+pub use private::{CloseTime, ClosedOrdersRequest, ClosedOrdersResponse, OpenOrdersRequest, OpenOrdersResponse, Order, OrderDetails, QueryOrdersRequest, QueryOrdersResponse};
+pub use private::{EditOrderRequest, EditOrderResponse, EditStatus};
+pub use private::{GetTradeBalanceRequest, GetTradeBalanceResponse, PairFee, TradeVolumeRequest, TradeVolumeResponse};
+pub use private::{LedgerEntry, LedgerType, LedgersRequest, LedgersResponse, QueryLedgersRequest, QueryLedgersResponse};
+pub use private::{OwnTrade, QueryTradesRequest, QueryTradesResponse, TradeFilter, TradesHistoryRequest, TradesHistoryResponse};
+pub use public::{Asset, AssetPair, AssetPairsRequest, AssetPairsResponse, AssetsRequest, AssetsResponse, FeeTier};
+pub use public::{Candle, OhlcRequest, OhlcResponse, Spread, SpreadRequest, SpreadResponse};
+pub use public::{Depth, DepthLevel, DepthRequest, DepthResponse, Trade, TradeOrderType, TradesRequest, TradesResponse};
+pub use public::{GetServerTimeRequest, GetServerTimeResponse, SystemStatus, SystemStatusRequest, SystemStatusResponse};
+pub use public::{LastTrade, Ticker, TickerLevel, TickerRequest, TickerResponse, TradeCount, Window};
 
 use serde::de::DeserializeOwned;
 use serde::Serialize;
@@ -11,6 +22,8 @@ pub trait Request: Serialize {
     const SIGNED: bool = false;
     const ENDPOINT: &'static str;
     const HAS_PAYLOAD: bool = true;
+    // Sends the payload as JSON instead of a form, for requests with nested parameters
+    const JSON_BODY: bool = false;
     type Response: DeserializeOwned;
 
     #[inline]
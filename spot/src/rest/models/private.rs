@@ -1,20 +1,1089 @@
 use super::Request;
-use serde::{Deserialize, Serialize};
+use crate::common::de::{comma_list, map_string_or_f64, option_string_or_f64, string_or_f64};
+use crate::common::{Currency, OrderFlag, OrderStatus, OrderTime, OrderType, Price, Side, TimeInForce, Trigger};
+use crate::errors::KrakenError;
+use chrono::{DateTime, Utc};
+use fehler::{throw, throws};
+use kraken_core::Paginated;
+use serde::de::Error as DeError;
+use serde::ser::{Error as SerError, SerializeMap};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{to_value, Map, Value};
+use std::collections::HashMap;
+use std::time::Duration;
 
 #[derive(Serialize, Debug, Clone)]
+pub struct BalanceRequest;
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(transparent)]
+pub struct BalanceResponse {
+    #[serde(deserialize_with = "map_string_or_f64")]
+    pub balances: HashMap<Currency, f64>,
+}
+
+impl Request for BalanceRequest {
+    const SIGNED: bool = true;
+    const ENDPOINT: &'static str = "/0/private/Balance";
+    const HAS_PAYLOAD: bool = true;
+    type Response = BalanceResponse;
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct BalanceExRequest;
+
+pub type BalanceExResponse = HashMap<Currency, ExtendedBalance>;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ExtendedBalance {
+    #[serde(deserialize_with = "string_or_f64")]
+    pub balance: f64,
+    // Reserved by open orders
+    #[serde(default, deserialize_with = "string_or_f64")]
+    pub hold_trade: f64,
+    #[serde(default, deserialize_with = "option_string_or_f64")]
+    pub credit: Option<f64>,
+    #[serde(default, deserialize_with = "option_string_or_f64")]
+    pub credit_used: Option<f64>,
+}
+
+impl ExtendedBalance {
+    // What can be traded or withdrawn, as defined in Kraken's docs
+    pub fn available(&self) -> f64 {
+        self.balance + self.credit.unwrap_or(0.) - self.credit_used.unwrap_or(0.) - self.hold_trade
+    }
+}
+
+impl Request for BalanceExRequest {
+    const SIGNED: bool = true;
+    const ENDPOINT: &'static str = "/0/private/BalanceEx";
+    const HAS_PAYLOAD: bool = true;
+    type Response = BalanceExResponse;
+}
+
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct TradeVolumeRequest {
+    // Comma separated, e.g. "XBTUSD,ETHUSD". Fees are only returned for these pairs.
+    pub pair: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TradeVolumeResponse {
+    // The currency `volume` is counted in
+    pub currency: Currency,
+    // 30 day volume
+    #[serde(deserialize_with = "string_or_f64")]
+    pub volume: f64,
+    // Keyed by pair name, e.g. "XXBTZUSD"
+    #[serde(default)]
+    pub fees: HashMap<String, PairFee>,
+    #[serde(default)]
+    pub fees_maker: HashMap<String, PairFee>,
+}
+
+// Fees are in percent
+#[derive(Deserialize, Debug, Clone)]
+pub struct PairFee {
+    #[serde(deserialize_with = "string_or_f64")]
+    pub fee: f64,
+    #[serde(rename = "minfee", deserialize_with = "string_or_f64")]
+    pub min_fee: f64,
+    #[serde(rename = "maxfee", deserialize_with = "string_or_f64")]
+    pub max_fee: f64,
+    // Not set at the highest tier
+    #[serde(rename = "nextfee", default, deserialize_with = "option_string_or_f64")]
+    pub next_fee: Option<f64>,
+    #[serde(rename = "nextvolume", default, deserialize_with = "option_string_or_f64")]
+    pub next_volume: Option<f64>,
+    #[serde(rename = "tiervolume", default, deserialize_with = "option_string_or_f64")]
+    pub tier_volume: Option<f64>,
+}
+
+impl Request for TradeVolumeRequest {
+    const SIGNED: bool = true;
+    const ENDPOINT: &'static str = "/0/private/TradeVolume";
+    const HAS_PAYLOAD: bool = true;
+    type Response = TradeVolumeResponse;
+}
+
+#[derive(Serialize, Debug, Clone, Default)]
 pub struct GetTradeBalanceRequest {
-    pub aclass: String,
-    pub asset: String,
+    // Only "currency" is supported, which is also the default
+    pub aclass: Option<String>,
+    // The asset balances are denominated in, ZUSD by default
+    pub asset: Option<String>,
 }
 
+// Field names as sent by Kraken
 #[derive(Deserialize, Debug, Clone)]
 pub struct GetTradeBalanceResponse {
+    // Equivalent balance, all currencies combined
+    #[serde(deserialize_with = "string_or_f64")]
     pub eb: f64,
+    // Trade balance, the equity currencies combined
+    #[serde(deserialize_with = "string_or_f64")]
+    pub tb: f64,
+    // Margin amount of open positions
+    #[serde(deserialize_with = "string_or_f64")]
+    pub m: f64,
+    // Unrealized net profit/loss of open positions
+    #[serde(deserialize_with = "string_or_f64")]
+    pub n: f64,
+    // Cost basis of open positions
+    #[serde(deserialize_with = "string_or_f64")]
+    pub c: f64,
+    // Current floating valuation of open positions
+    #[serde(deserialize_with = "string_or_f64")]
+    pub v: f64,
+    // Equity, trade balance plus unrealized net profit/loss
+    #[serde(deserialize_with = "string_or_f64")]
+    pub e: f64,
+    // Free margin, equity minus initial margin
+    #[serde(deserialize_with = "string_or_f64")]
+    pub mf: f64,
+    // Margin level in percent, only present with open positions
+    #[serde(default, deserialize_with = "option_string_or_f64")]
+    pub ml: Option<f64>,
+    // Value of unfilled and partially filled orders, not always present
+    #[serde(default, deserialize_with = "option_string_or_f64")]
+    pub uv: Option<f64>,
 }
 
 impl Request for GetTradeBalanceRequest {
     const SIGNED: bool = true;
     const ENDPOINT: &'static str = "/0/private/TradeBalance";
     const HAS_PAYLOAD: bool = true;
-    type Response = Vec<GetTradeBalanceResponse>;
+    type Response = GetTradeBalanceResponse;
+}
+
+// Construct with one of the order type constructors, which take exactly the prices that order type requires.
+#[derive(Serialize, Debug, Clone)]
+pub struct AddOrderRequest {
+    #[serde(rename = "ordertype")]
+    order_type: OrderType,
+    #[serde(rename = "type")]
+    side: Side,
+    volume: f64,
+    pair: String,
+    price: Option<Price>,
+    price2: Option<Price>,
+    trigger: Option<Trigger>,
+    leverage: Option<u32>,
+    reduce_only: Option<bool>,
+    #[serde(skip_serializing_if = "Vec::is_empty", serialize_with = "comma_separated")]
+    oflags: Vec<OrderFlag>,
+    #[serde(rename = "timeinforce")]
+    time_in_force: Option<TimeInForce>,
+    #[serde(rename = "starttm")]
+    start_time: Option<OrderTime>,
+    #[serde(rename = "expiretm")]
+    expire_time: Option<OrderTime>,
+    userref: Option<i32>,
+    cl_ord_id: Option<String>,
+    validate: Option<bool>,
+}
+
+impl AddOrderRequest {
+    fn new(order_type: OrderType, pair: &str, side: Side, volume: f64) -> Self {
+        Self {
+            order_type,
+            side,
+            volume,
+            pair: pair.into(),
+            price: None,
+            price2: None,
+            trigger: None,
+            leverage: None,
+            reduce_only: None,
+            oflags: vec![],
+            time_in_force: None,
+            start_time: None,
+            expire_time: None,
+            userref: None,
+            cl_ord_id: None,
+            validate: None,
+        }
+    }
+
+    fn with_prices(order_type: OrderType, pair: &str, side: Side, price: Price, price2: Option<Price>, volume: f64) -> Self {
+        Self {
+            price: Some(price),
+            price2,
+            ..Self::new(order_type, pair, side, volume)
+        }
+    }
+
+    pub fn market(pair: &str, side: Side, volume: f64) -> Self {
+        Self::new(OrderType::Market, pair, side, volume)
+    }
+
+    pub fn limit(pair: &str, side: Side, price: Price, volume: f64) -> Self {
+        Self::with_prices(OrderType::Limit, pair, side, price, None, volume)
+    }
+
+    pub fn stop_loss(pair: &str, side: Side, trigger_price: Price, volume: f64) -> Self {
+        Self::with_prices(OrderType::StopLoss, pair, side, trigger_price, None, volume)
+    }
+
+    pub fn take_profit(pair: &str, side: Side, trigger_price: Price, volume: f64) -> Self {
+        Self::with_prices(OrderType::TakeProfit, pair, side, trigger_price, None, volume)
+    }
+
+    pub fn stop_loss_limit(pair: &str, side: Side, trigger_price: Price, limit_price: Price, volume: f64) -> Self {
+        Self::with_prices(OrderType::StopLossLimit, pair, side, trigger_price, Some(limit_price), volume)
+    }
+
+    pub fn take_profit_limit(pair: &str, side: Side, trigger_price: Price, limit_price: Price, volume: f64) -> Self {
+        Self::with_prices(OrderType::TakeProfitLimit, pair, side, trigger_price, Some(limit_price), volume)
+    }
+
+    // Kraken requires the offset to be a `Price::plus`
+    pub fn trailing_stop(pair: &str, side: Side, offset: Price, volume: f64) -> Self {
+        Self::with_prices(OrderType::TrailingStop, pair, side, offset, None, volume)
+    }
+
+    // `limit_offset` is relative to the trigger price and must be a `Price::plus` or `Price::minus`
+    pub fn trailing_stop_limit(pair: &str, side: Side, offset: Price, limit_offset: Price, volume: f64) -> Self {
+        Self::with_prices(OrderType::TrailingStopLimit, pair, side, offset, Some(limit_offset), volume)
+    }
+
+    // Closes a margin position, a volume of 0 closes all of it
+    pub fn settle_position(pair: &str, side: Side, volume: f64) -> Self {
+        Self::new(OrderType::SettlePosition, pair, side, volume)
+    }
+
+    pub fn trigger(self, trigger: Trigger) -> Self {
+        Self { trigger: Some(trigger), ..self }
+    }
+
+    // Places a margin order
+    pub fn leverage(self, leverage: u32) -> Self {
+        Self { leverage: Some(leverage), ..self }
+    }
+
+    pub fn reduce_only(self, reduce_only: bool) -> Self {
+        Self {
+            reduce_only: Some(reduce_only),
+            ..self
+        }
+    }
+
+    pub fn flag(mut self, flag: OrderFlag) -> Self {
+        if !self.oflags.contains(&flag) {
+            self.oflags.push(flag);
+        }
+        self
+    }
+
+    pub fn time_in_force(self, time_in_force: TimeInForce) -> Self {
+        Self {
+            time_in_force: Some(time_in_force),
+            ..self
+        }
+    }
+
+    pub fn start_time(self, start_time: OrderTime) -> Self {
+        Self {
+            start_time: Some(start_time),
+            ..self
+        }
+    }
+
+    pub fn expire_time(self, expire_time: OrderTime) -> Self {
+        Self {
+            expire_time: Some(expire_time),
+            ..self
+        }
+    }
+
+    pub fn userref(self, userref: i32) -> Self {
+        Self { userref: Some(userref), ..self }
+    }
+
+    pub fn cl_ord_id(self, cl_ord_id: &str) -> Self {
+        Self {
+            cl_ord_id: Some(cl_ord_id.into()),
+            ..self
+        }
+    }
+
+    // Only validates the order, nothing is placed and the response has no txid
+    pub fn validate(self, validate: bool) -> Self {
+        Self { validate: Some(validate), ..self }
+    }
+}
+
+fn comma_separated<S: Serializer>(flags: &[OrderFlag], serializer: S) -> Result<S::Ok, S::Error> {
+    let flags: Vec<_> = flags.iter().map(|f| f.to_string()).collect();
+    serializer.serialize_str(&flags.join(","))
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct AddOrderResponse {
+    pub descr: OrderDescription,
+    // Not set when only validating
+    #[serde(default)]
+    pub txid: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct OrderDescription {
+    pub order: String,
+    pub close: Option<String>,
+}
+
+impl Request for AddOrderRequest {
+    const SIGNED: bool = true;
+    const ENDPOINT: &'static str = "/0/private/AddOrder";
+    const HAS_PAYLOAD: bool = true;
+    type Response = AddOrderResponse;
+}
+
+// Up to 15 orders on one pair. Kraken validates the whole batch before placing any of it.
+#[derive(Debug, Clone)]
+pub struct AddOrderBatchRequest {
+    pair: String,
+    orders: Vec<AddOrderRequest>,
+    validate: Option<bool>,
+}
+
+impl AddOrderBatchRequest {
+    pub const MAX_ORDERS: usize = 15;
+
+    #[throws(KrakenError)]
+    pub fn new(orders: Vec<AddOrderRequest>) -> Self {
+        let pair = match orders.first() {
+            Some(order) => order.pair.clone(),
+            None => throw!(KrakenError::InvalidOrderBatch("no orders".into())),
+        };
+        if orders.len() > Self::MAX_ORDERS {
+            throw!(KrakenError::InvalidOrderBatch(format!("{} orders, at most {}", orders.len(), Self::MAX_ORDERS)));
+        }
+        if let Some(order) = orders.iter().find(|o| o.pair != pair) {
+            throw!(KrakenError::InvalidOrderBatch(format!("orders on both {} and {}", pair, order.pair)));
+        }
+
+        Self { pair, orders, validate: None }
+    }
+
+    // Only validates the orders, nothing is placed
+    pub fn validate(self, validate: bool) -> Self {
+        Self { validate: Some(validate), ..self }
+    }
+
+    // In the same order as `AddOrderBatchResponse::orders`
+    pub fn orders(&self) -> &[AddOrderRequest] {
+        &self.orders
+    }
+}
+
+// The pair and validate flag are set once for the batch, not per order
+impl Serialize for AddOrderBatchRequest {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut orders = vec![];
+        for order in &self.orders {
+            let fields = match to_value(order).map_err(SerError::custom)? {
+                Value::Object(fields) => fields,
+                _ => unreachable!("AddOrderRequest serializes to an object"),
+            };
+            let fields: Map<_, _> = fields.into_iter().filter(|(k, v)| k != "pair" && k != "validate" && !v.is_null()).collect();
+            orders.push(fields);
+        }
+
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("orders", &orders)?;
+        map.serialize_entry("pair", &self.pair)?;
+        if let Some(validate) = self.validate {
+            map.serialize_entry("validate", &validate)?;
+        }
+        map.end()
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct AddOrderBatchResponse {
+    // One result per order, in the order they were sent
+    pub orders: Vec<BatchOrderResult>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct BatchOrderResult {
+    pub descr: Option<OrderDescription>,
+    pub txid: Option<String>,
+    pub error: Option<String>,
+}
+
+impl Request for AddOrderBatchRequest {
+    const SIGNED: bool = true;
+    const ENDPOINT: &'static str = "/0/private/AddOrderBatch";
+    const HAS_PAYLOAD: bool = true;
+    const JSON_BODY: bool = true;
+    type Response = AddOrderBatchResponse;
+}
+
+// Kraken cancels the order and places a new one with a new txid, only the set fields change.
+#[derive(Serialize, Debug, Clone)]
+pub struct EditOrderRequest {
+    // The txid or userref of the order
+    txid: String,
+    pair: String,
+    volume: Option<f64>,
+    price: Option<Price>,
+    price2: Option<Price>,
+    #[serde(skip_serializing_if = "Vec::is_empty", serialize_with = "comma_separated")]
+    oflags: Vec<OrderFlag>,
+    // Set on the new order
+    userref: Option<i32>,
+    validate: Option<bool>,
+}
+
+impl EditOrderRequest {
+    pub fn new(txid: &str, pair: &str) -> Self {
+        Self {
+            txid: txid.into(),
+            pair: pair.into(),
+            volume: None,
+            price: None,
+            price2: None,
+            oflags: vec![],
+            userref: None,
+            validate: None,
+        }
+    }
+
+    pub fn from_userref(userref: i32, pair: &str) -> Self {
+        Self::new(&userref.to_string(), pair)
+    }
+
+    // Amends `txid` to the volume, prices and flags of `order`
+    pub fn replace(txid: &str, order: &AddOrderRequest) -> Self {
+        Self {
+            volume: Some(order.volume),
+            price: order.price,
+            price2: order.price2,
+            oflags: order.oflags.clone(),
+            userref: order.userref,
+            ..Self::new(txid, &order.pair)
+        }
+    }
+
+    pub fn volume(self, volume: f64) -> Self {
+        Self { volume: Some(volume), ..self }
+    }
+
+    pub fn price(self, price: Price) -> Self {
+        Self { price: Some(price), ..self }
+    }
+
+    pub fn price2(self, price2: Price) -> Self {
+        Self { price2: Some(price2), ..self }
+    }
+
+    pub fn flag(mut self, flag: OrderFlag) -> Self {
+        if !self.oflags.contains(&flag) {
+            self.oflags.push(flag);
+        }
+        self
+    }
+
+    pub fn userref(self, userref: i32) -> Self {
+        Self { userref: Some(userref), ..self }
+    }
+
+    pub fn validate(self, validate: bool) -> Self {
+        Self { validate: Some(validate), ..self }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct EditOrderResponse {
+    pub status: EditStatus,
+    // The txid of the new order
+    pub txid: Option<String>,
+    #[serde(rename = "originaltxid")]
+    pub original_txid: Option<String>,
+    pub descr: Option<OrderDescription>,
+    #[serde(default, deserialize_with = "option_string_or_f64")]
+    pub volume: Option<f64>,
+    #[serde(default, deserialize_with = "option_string_or_f64")]
+    pub price: Option<f64>,
+    #[serde(default, deserialize_with = "option_string_or_f64")]
+    pub price2: Option<f64>,
+    pub orders_cancelled: Option<u32>,
+    #[serde(rename = "newuserref")]
+    pub new_userref: Option<i32>,
+    #[serde(rename = "olduserref")]
+    pub old_userref: Option<i32>,
+    pub error_message: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum EditStatus {
+    Ok,
+    Err,
+}
+
+impl Request for EditOrderRequest {
+    const SIGNED: bool = true;
+    const ENDPOINT: &'static str = "/0/private/EditOrder";
+    const HAS_PAYLOAD: bool = true;
+    type Response = EditOrderResponse;
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct CancelOrderRequest {
+    // The txid or userref of the order
+    txid: Option<String>,
+    cl_ord_id: Option<String>,
+}
+
+impl CancelOrderRequest {
+    pub fn from_txid(txid: &str) -> Self {
+        Self {
+            txid: Some(txid.into()),
+            cl_ord_id: None,
+        }
+    }
+
+    // Cancels every order with this userref
+    pub fn from_userref(userref: i32) -> Self {
+        Self::from_txid(&userref.to_string())
+    }
+
+    pub fn from_cl_ord_id(cl_ord_id: &str) -> Self {
+        Self {
+            txid: None,
+            cl_ord_id: Some(cl_ord_id.into()),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct CancelOrderResponse {
+    pub count: u32,
+    #[serde(default)]
+    pub pending: bool,
+}
+
+impl Request for CancelOrderRequest {
+    const SIGNED: bool = true;
+    const ENDPOINT: &'static str = "/0/private/CancelOrder";
+    const HAS_PAYLOAD: bool = true;
+    type Response = CancelOrderResponse;
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct CancelAllRequest;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct CancelAllResponse {
+    pub count: u32,
+}
+
+impl Request for CancelAllRequest {
+    const SIGNED: bool = true;
+    const ENDPOINT: &'static str = "/0/private/CancelAll";
+    const HAS_PAYLOAD: bool = true;
+    type Response = CancelAllResponse;
+}
+
+// The dead man's switch: cancels all orders unless called again within `timeout`. See `Kraken::heartbeat`.
+#[derive(Serialize, Debug, Clone)]
+pub struct CancelAllOrdersAfterRequest {
+    // In seconds, 0 disarms the switch
+    timeout: u64,
+}
+
+impl CancelAllOrdersAfterRequest {
+    // Rounded up to whole seconds, so a sub-second timeout still arms the switch
+    pub fn new(timeout: Duration) -> Self {
+        let secs = timeout.as_secs() + (timeout.subsec_nanos() > 0) as u64;
+        Self { timeout: secs }
+    }
+
+    pub fn disarm() -> Self {
+        Self { timeout: 0 }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct CancelAllOrdersAfterResponse {
+    #[serde(rename = "currentTime")]
+    pub current_time: DateTime<Utc>,
+    // Not set once disarmed
+    #[serde(rename = "triggerTime", deserialize_with = "trigger_time")]
+    pub trigger_time: Option<DateTime<Utc>>,
+}
+
+// Kraken sends "0" when the switch is disarmed
+fn trigger_time<'de, D>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    if s == "0" {
+        return Ok(None);
+    }
+    s.parse().map(Some).map_err(DeError::custom)
+}
+
+impl Request for CancelAllOrdersAfterRequest {
+    const SIGNED: bool = true;
+    const ENDPOINT: &'static str = "/0/private/CancelAllOrdersAfter";
+    const HAS_PAYLOAD: bool = true;
+    type Response = CancelAllOrdersAfterResponse;
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Order {
+    pub refid: Option<String>,
+    pub userref: Option<i32>,
+    pub cl_ord_id: Option<String>,
+    pub status: OrderStatus,
+    // Unix timestamps with sub-second precision, 0 if not set
+    #[serde(rename = "opentm")]
+    pub open_time: f64,
+    #[serde(rename = "starttm")]
+    pub start_time: f64,
+    #[serde(rename = "expiretm")]
+    pub expire_time: f64,
+    #[serde(rename = "closetm")]
+    pub close_time: Option<f64>,
+    // Why the order was closed or canceled
+    pub reason: Option<String>,
+    pub descr: OrderDetails,
+    #[serde(rename = "vol", deserialize_with = "string_or_f64")]
+    pub volume: f64,
+    #[serde(deserialize_with = "string_or_f64")]
+    pub vol_exec: f64,
+    // In the quote currency
+    #[serde(deserialize_with = "string_or_f64")]
+    pub cost: f64,
+    #[serde(deserialize_with = "string_or_f64")]
+    pub fee: f64,
+    #[serde(rename = "price", deserialize_with = "string_or_f64")]
+    pub avg_price: f64,
+    #[serde(rename = "stopprice", default, deserialize_with = "option_string_or_f64")]
+    pub stop_price: Option<f64>,
+    #[serde(rename = "limitprice", default, deserialize_with = "option_string_or_f64")]
+    pub limit_price: Option<f64>,
+    // e.g. "stopped", "touched", "liquidated", "partial" or "amended"
+    #[serde(default, deserialize_with = "comma_list")]
+    pub misc: Vec<String>,
+    #[serde(default, deserialize_with = "comma_list")]
+    pub oflags: Vec<String>,
+    // Only with `trades` set in the request
+    #[serde(default)]
+    pub trades: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct OrderDetails {
+    pub pair: String,
+    #[serde(rename = "type")]
+    pub side: Side,
+    #[serde(rename = "ordertype")]
+    pub order_type: OrderType,
+    #[serde(deserialize_with = "string_or_f64")]
+    pub price: f64,
+    #[serde(deserialize_with = "string_or_f64")]
+    pub price2: f64,
+    // e.g. "none" or "5:1"
+    pub leverage: String,
+    pub order: String,
+    #[serde(default)]
+    pub close: String,
+}
+
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct OpenOrdersRequest {
+    // Include the trade ids of each order
+    pub trades: Option<bool>,
+    pub userref: Option<i32>,
+    pub cl_ord_id: Option<String>,
+}
+
+// Keyed by txid
+#[derive(Deserialize, Debug, Clone)]
+pub struct OpenOrdersResponse {
+    pub open: HashMap<String, Order>,
+}
+
+impl Request for OpenOrdersRequest {
+    const SIGNED: bool = true;
+    const ENDPOINT: &'static str = "/0/private/OpenOrders";
+    const HAS_PAYLOAD: bool = true;
+    type Response = OpenOrdersResponse;
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CloseTime {
+    Open,
+    Close,
+    Both,
+}
+
+// Kraken returns 50 orders per page, use `Kraken::stream` to read all of them
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct ClosedOrdersRequest {
+    pub trades: Option<bool>,
+    pub userref: Option<i32>,
+    pub cl_ord_id: Option<String>,
+    // Unix timestamps, exclusive
+    pub start: Option<i64>,
+    pub end: Option<i64>,
+    pub ofs: Option<u32>,
+    // Which time `start` and `end` apply to, both if not set
+    #[serde(rename = "closetime")]
+    pub close_time: Option<CloseTime>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ClosedOrdersResponse {
+    // Keyed by txid
+    pub closed: HashMap<String, Order>,
+    // Matching orders across all pages
+    pub count: u32,
+}
+
+impl Request for ClosedOrdersRequest {
+    const SIGNED: bool = true;
+    const ENDPOINT: &'static str = "/0/private/ClosedOrders";
+    const HAS_PAYLOAD: bool = true;
+    type Response = ClosedOrdersResponse;
+}
+
+impl Paginated for ClosedOrdersRequest {
+    type Page = ClosedOrdersResponse;
+    type Item = (String, Order);
+
+    fn next_page(&self, resp: &Self::Page) -> Option<Self> {
+        let ofs = next_ofs(self.ofs, resp.closed.len(), Some(resp.count))?;
+        Some(Self { ofs: Some(ofs), ..self.clone() })
+    }
+
+    fn into_items(resp: Self::Page) -> Vec<Self::Item> {
+        newest_first(resp.closed, |order| order.open_time)
+    }
+}
+
+// Rows newest first, like Kraken's pages
+fn newest_first<T>(rows: HashMap<String, T>, time: impl Fn(&T) -> f64) -> Vec<(String, T)> {
+    let mut rows: Vec<_> = rows.into_iter().collect();
+    rows.sort_by(|(_, a), (_, b)| time(b).total_cmp(&time(a)));
+    rows
+}
+
+// The offset of the page after one with `len` rows, None once the end is reached. Rows added or closed
+// while paging shift the offsets, so pin `end` for a consistent read.
+fn next_ofs(ofs: Option<u32>, len: usize, count: Option<u32>) -> Option<u32> {
+    let ofs = ofs.unwrap_or(0) + len as u32;
+    match count {
+        _ if len == 0 => None,
+        Some(count) if ofs >= count => None,
+        _ => Some(ofs),
+    }
+}
+
+// Joins 1 to `max` ids into Kraken's comma separated list
+#[throws(KrakenError)]
+fn id_list<S: AsRef<str>>(ids: &[S], max: usize) -> String {
+    if ids.is_empty() {
+        throw!(KrakenError::InvalidIdList("no ids".into()));
+    }
+    if ids.len() > max {
+        throw!(KrakenError::InvalidIdList(format!("{} ids, at most {}", ids.len(), max)));
+    }
+    ids.iter().map(|id| id.as_ref()).collect::<Vec<_>>().join(",")
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct QueryOrdersRequest {
+    // Comma separated, at most `MAX_TXIDS`
+    pub txid: String,
+    pub trades: Option<bool>,
+    pub userref: Option<i32>,
+}
+
+impl QueryOrdersRequest {
+    pub const MAX_TXIDS: usize = 50;
+
+    #[throws(KrakenError)]
+    pub fn new<S: AsRef<str>>(txids: &[S]) -> Self {
+        Self {
+            txid: id_list(txids, Self::MAX_TXIDS)?,
+            trades: None,
+            userref: None,
+        }
+    }
+}
+
+// Keyed by txid
+pub type QueryOrdersResponse = HashMap<String, Order>;
+
+impl Request for QueryOrdersRequest {
+    const SIGNED: bool = true;
+    const ENDPOINT: &'static str = "/0/private/QueryOrders";
+    const HAS_PAYLOAD: bool = true;
+    type Response = QueryOrdersResponse;
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TradeFilter {
+    #[serde(rename = "all")]
+    All,
+    #[serde(rename = "any position")]
+    AnyPosition,
+    #[serde(rename = "closed position")]
+    ClosedPosition,
+    #[serde(rename = "closing position")]
+    ClosingPosition,
+    #[serde(rename = "no position")]
+    NoPosition,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct OwnTrade {
+    #[serde(rename = "ordertxid")]
+    pub order_txid: String,
+    // The position this trade opened or closed, if any
+    #[serde(rename = "postxid")]
+    pub pos_txid: Option<String>,
+    pub pair: String,
+    // Unix timestamp with sub-second precision
+    pub time: f64,
+    #[serde(rename = "type")]
+    pub side: Side,
+    #[serde(rename = "ordertype")]
+    pub order_type: OrderType,
+    #[serde(deserialize_with = "string_or_f64")]
+    pub price: f64,
+    // In the quote currency
+    #[serde(deserialize_with = "string_or_f64")]
+    pub cost: f64,
+    #[serde(deserialize_with = "string_or_f64")]
+    pub fee: f64,
+    #[serde(rename = "vol", deserialize_with = "string_or_f64")]
+    pub volume: f64,
+    // Initial margin, 0 for trades without leverage
+    #[serde(default, deserialize_with = "string_or_f64")]
+    pub margin: f64,
+    #[serde(default, deserialize_with = "option_string_or_f64")]
+    pub leverage: Option<f64>,
+    // e.g. "closing"
+    #[serde(default, deserialize_with = "comma_list")]
+    pub misc: Vec<String>,
+    pub trade_id: Option<u64>,
+    pub maker: Option<bool>,
+    // Only with `ledgers` set in the request
+    #[serde(default)]
+    pub ledgers: Vec<String>,
+    // Only for trades that opened a position: "open" or "closed"
+    #[serde(rename = "posstatus")]
+    pub pos_status: Option<String>,
+}
+
+// Kraken returns 50 trades per page, use `Kraken::stream` to read all of them
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct TradesHistoryRequest {
+    #[serde(rename = "type")]
+    pub filter: Option<TradeFilter>,
+    // Include the trades related to each position
+    pub trades: Option<bool>,
+    // Unix timestamps or trade ids, exclusive
+    pub start: Option<i64>,
+    pub end: Option<i64>,
+    pub ofs: Option<u32>,
+    // Include the ledger ids of each trade
+    pub ledgers: Option<bool>,
+    // Merge the taker trades filled by one order into a single trade, true if not set
+    pub consolidate_taker: Option<bool>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TradesHistoryResponse {
+    // Keyed by trade txid
+    pub trades: HashMap<String, OwnTrade>,
+    // Matching trades across all pages
+    pub count: u32,
+}
+
+impl Request for TradesHistoryRequest {
+    const SIGNED: bool = true;
+    const ENDPOINT: &'static str = "/0/private/TradesHistory";
+    const HAS_PAYLOAD: bool = true;
+    type Response = TradesHistoryResponse;
+}
+
+impl Paginated for TradesHistoryRequest {
+    type Page = TradesHistoryResponse;
+    type Item = (String, OwnTrade);
+
+    fn next_page(&self, resp: &Self::Page) -> Option<Self> {
+        let ofs = next_ofs(self.ofs, resp.trades.len(), Some(resp.count))?;
+        Some(Self { ofs: Some(ofs), ..self.clone() })
+    }
+
+    fn into_items(resp: Self::Page) -> Vec<Self::Item> {
+        newest_first(resp.trades, |trade| trade.time)
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct QueryTradesRequest {
+    // Comma separated, at most `MAX_TXIDS`
+    pub txid: String,
+    // Include the trades related to each position
+    pub trades: Option<bool>,
+}
+
+impl QueryTradesRequest {
+    pub const MAX_TXIDS: usize = 20;
+
+    #[throws(KrakenError)]
+    pub fn new<S: AsRef<str>>(txids: &[S]) -> Self {
+        Self {
+            txid: id_list(txids, Self::MAX_TXIDS)?,
+            trades: None,
+        }
+    }
+}
+
+// Keyed by trade txid
+pub type QueryTradesResponse = HashMap<String, OwnTrade>;
+
+impl Request for QueryTradesRequest {
+    const SIGNED: bool = true;
+    const ENDPOINT: &'static str = "/0/private/QueryTrades";
+    const HAS_PAYLOAD: bool = true;
+    type Response = QueryTradesResponse;
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LedgerType {
+    None,
+    Trade,
+    Deposit,
+    Withdrawal,
+    Transfer,
+    Margin,
+    Adjustment,
+    Rollover,
+    Spend,
+    Receive,
+    Settled,
+    Credit,
+    Staking,
+    Reward,
+    Dividend,
+    Sale,
+    Conversion,
+    NftTrade,
+    NftCreatorFee,
+    NftRebate,
+    CustodyTransfer,
+    // Types Kraken added after this list was written
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct LedgerEntry {
+    // The trade, deposit or withdrawal behind the entry
+    pub refid: String,
+    // Unix timestamp with sub-second precision
+    pub time: f64,
+    #[serde(rename = "type")]
+    pub ledger_type: LedgerType,
+    // e.g. "spottostaking" for transfers, may be empty
+    #[serde(default)]
+    pub subtype: String,
+    pub aclass: String,
+    pub asset: Currency,
+    #[serde(deserialize_with = "string_or_f64")]
+    pub amount: f64,
+    #[serde(deserialize_with = "string_or_f64")]
+    pub fee: f64,
+    // The asset's balance after the entry
+    #[serde(deserialize_with = "string_or_f64")]
+    pub balance: f64,
+}
+
+// Kraken returns 50 entries per page, use `Kraken::stream` to read all of them
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct LedgersRequest {
+    // Comma separated, all assets if not set
+    pub asset: Option<String>,
+    pub aclass: Option<String>,
+    // All types if not set
+    #[serde(rename = "type")]
+    pub ledger_type: Option<LedgerType>,
+    // Unix timestamps or ledger ids, exclusive
+    pub start: Option<i64>,
+    pub end: Option<i64>,
+    pub ofs: Option<u32>,
+    // Skips counting the entries, which is faster on large ledgers
+    pub without_count: Option<bool>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct LedgersResponse {
+    // Keyed by ledger id
+    pub ledger: HashMap<String, LedgerEntry>,
+    // Matching entries across all pages, not set with `without_count`
+    pub count: Option<u32>,
+}
+
+impl Request for LedgersRequest {
+    const SIGNED: bool = true;
+    const ENDPOINT: &'static str = "/0/private/Ledgers";
+    const HAS_PAYLOAD: bool = true;
+    type Response = LedgersResponse;
+}
+
+impl Paginated for LedgersRequest {
+    type Page = LedgersResponse;
+    type Item = (String, LedgerEntry);
+
+    fn next_page(&self, resp: &Self::Page) -> Option<Self> {
+        let ofs = next_ofs(self.ofs, resp.ledger.len(), resp.count)?;
+        Some(Self { ofs: Some(ofs), ..self.clone() })
+    }
+
+    fn into_items(resp: Self::Page) -> Vec<Self::Item> {
+        newest_first(resp.ledger, |entry| entry.time)
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct QueryLedgersRequest {
+    // Comma separated, at most `MAX_IDS`
+    pub id: String,
+    // Include the trades related to each entry
+    pub trades: Option<bool>,
+}
+
+impl QueryLedgersRequest {
+    pub const MAX_IDS: usize = 20;
+
+    #[throws(KrakenError)]
+    pub fn new<S: AsRef<str>>(ids: &[S]) -> Self {
+        Self {
+            id: id_list(ids, Self::MAX_IDS)?,
+            trades: None,
+        }
+    }
+}
+
+// Keyed by ledger id
+pub type QueryLedgersResponse = HashMap<String, LedgerEntry>;
+
+impl Request for QueryLedgersRequest {
+    const SIGNED: bool = true;
+    const ENDPOINT: &'static str = "/0/private/QueryLedgers";
+    const HAS_PAYLOAD: bool = true;
+    type Response = QueryLedgersResponse;
 }
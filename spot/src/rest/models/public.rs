@@ -1,6 +1,12 @@
 use super::Request;
+use crate::common::de::{option_string_or_f64, string_or_f64};
+use crate::common::Side;
 use chrono::{DateTime, Utc};
+use serde::de::{Error as DeError, MapAccess, Visitor};
 use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::marker::PhantomData;
 
 #[derive(Serialize, Debug, Clone)]
 pub struct GetServerTimeRequest;
@@ -19,6 +25,447 @@ impl Request for GetServerTimeRequest {
     type Response = GetServerTimeResponse;
 }
 
+#[derive(Serialize, Debug, Clone)]
+pub struct SystemStatusRequest;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct SystemStatusResponse {
+    pub status: SystemStatus,
+    pub timestamp: DateTime<Utc>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SystemStatus {
+    Online,
+    Maintenance,
+    CancelOnly,
+    PostOnly,
+}
+
+impl Request for SystemStatusRequest {
+    const SIGNED: bool = false;
+    const ENDPOINT: &'static str = "/0/public/SystemStatus";
+    const HAS_PAYLOAD: bool = false;
+    type Response = SystemStatusResponse;
+}
+
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct AssetsRequest {
+    // Comma separated, e.g. "XBT,ETH". All assets if not set.
+    pub asset: Option<String>,
+    pub aclass: Option<String>,
+}
+
+// Keyed by the asset name, e.g. "XXBT"
+pub type AssetsResponse = HashMap<String, Asset>;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Asset {
+    pub aclass: String,
+    // e.g. "XBT"
+    pub altname: String,
+    pub decimals: u32,
+    pub display_decimals: u32,
+    pub collateral_value: Option<f64>,
+    pub status: Option<String>,
+}
+
+impl Request for AssetsRequest {
+    const SIGNED: bool = false;
+    const ENDPOINT: &'static str = "/0/public/Assets";
+    const HAS_PAYLOAD: bool = true;
+    type Response = AssetsResponse;
+}
+
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct AssetPairsRequest {
+    // Comma separated, e.g. "XBTUSD,ETHUSD". All pairs if not set.
+    pub pair: Option<String>,
+}
+
+// Keyed by the pair name, e.g. "XXBTZUSD"
+pub type AssetPairsResponse = HashMap<String, AssetPair>;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct AssetPair {
+    // e.g. "XBTUSD"
+    pub altname: String,
+    // e.g. "XBT/USD", missing for dark pool pairs
+    pub wsname: Option<String>,
+    pub aclass_base: String,
+    pub base: String,
+    pub aclass_quote: String,
+    pub quote: String,
+    pub lot: String,
+    pub cost_decimals: Option<u32>,
+    pub pair_decimals: u32,
+    pub lot_decimals: u32,
+    pub lot_multiplier: u32,
+    pub leverage_buy: Vec<u32>,
+    pub leverage_sell: Vec<u32>,
+    // Taker fees, also charged to makers on pairs without `fees_maker`
+    pub fees: Vec<FeeTier>,
+    #[serde(default)]
+    pub fees_maker: Vec<FeeTier>,
+    pub fee_volume_currency: String,
+    pub margin_call: u32,
+    pub margin_stop: u32,
+    #[serde(rename = "ordermin", default, deserialize_with = "option_string_or_f64")]
+    pub order_min: Option<f64>,
+    #[serde(rename = "costmin", default, deserialize_with = "option_string_or_f64")]
+    pub cost_min: Option<f64>,
+    #[serde(default, deserialize_with = "option_string_or_f64")]
+    pub tick_size: Option<f64>,
+    pub status: Option<String>,
+}
+
+// Kraken sends each tier as a [volume, percent fee] pair
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct FeeTier {
+    pub volume: f64,
+    pub fee: f64,
+}
+
+impl Request for AssetPairsRequest {
+    const SIGNED: bool = false;
+    const ENDPOINT: &'static str = "/0/public/AssetPairs";
+    const HAS_PAYLOAD: bool = true;
+    type Response = AssetPairsResponse;
+}
+
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct TickerRequest {
+    // Comma separated, e.g. "XBTUSD,ETHUSD". All pairs if not set.
+    pub pair: Option<String>,
+}
+
+// Keyed by the pair name, e.g. "XXBTZUSD"
+pub type TickerResponse = HashMap<String, Ticker>;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Ticker {
+    #[serde(rename = "a")]
+    pub ask: TickerLevel,
+    #[serde(rename = "b")]
+    pub bid: TickerLevel,
+    #[serde(rename = "c")]
+    pub last: LastTrade,
+    #[serde(rename = "v")]
+    pub volume: Window,
+    #[serde(rename = "p")]
+    pub vwap: Window,
+    #[serde(rename = "t")]
+    pub trades: TradeCount,
+    #[serde(rename = "l")]
+    pub low: Window,
+    #[serde(rename = "h")]
+    pub high: Window,
+    // Today's opening price
+    #[serde(rename = "o", deserialize_with = "string_or_f64")]
+    pub open: f64,
+}
+
+// [price, whole lot volume, lot volume]
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct TickerLevel {
+    #[serde(deserialize_with = "string_or_f64")]
+    pub price: f64,
+    #[serde(deserialize_with = "string_or_f64")]
+    pub whole_lot_volume: f64,
+    #[serde(deserialize_with = "string_or_f64")]
+    pub lot_volume: f64,
+}
+
+// [price, lot volume]
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct LastTrade {
+    #[serde(deserialize_with = "string_or_f64")]
+    pub price: f64,
+    #[serde(deserialize_with = "string_or_f64")]
+    pub lot_volume: f64,
+}
+
+// [today, last 24 hours]
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct Window {
+    #[serde(deserialize_with = "string_or_f64")]
+    pub today: f64,
+    #[serde(deserialize_with = "string_or_f64")]
+    pub last_24h: f64,
+}
+
+// [today, last 24 hours]
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct TradeCount {
+    pub today: u64,
+    pub last_24h: u64,
+}
+
+impl Request for TickerRequest {
+    const SIGNED: bool = false;
+    const ENDPOINT: &'static str = "/0/public/Ticker";
+    const HAS_PAYLOAD: bool = true;
+    type Response = TickerResponse;
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct OhlcRequest {
+    pub pair: String,
+    // In minutes, one of 1, 5, 15, 30, 60, 240, 1440, 10080 or 21600. 1 if not set.
+    pub interval: Option<u32>,
+    // Only candles after this unix timestamp, e.g. the `last` of a previous response
+    pub since: Option<i64>,
+}
+
+impl OhlcRequest {
+    pub fn new(pair: &str) -> Self {
+        OhlcRequest {
+            pair: pair.into(),
+            interval: None,
+            since: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OhlcResponse {
+    pub pair: String,
+    pub candles: Vec<Candle>,
+    pub last: i64,
+}
+
+impl<'de> Deserialize<'de> for OhlcResponse {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let Keyed { pair, data, last } = Keyed::deserialize(deserializer)?;
+        Ok(OhlcResponse { pair, candles: data, last })
+    }
+}
+
+// [time, open, high, low, close, vwap, volume, count]
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct Candle {
+    // Unix timestamp of the start of the interval
+    pub time: i64,
+    #[serde(deserialize_with = "string_or_f64")]
+    pub open: f64,
+    #[serde(deserialize_with = "string_or_f64")]
+    pub high: f64,
+    #[serde(deserialize_with = "string_or_f64")]
+    pub low: f64,
+    #[serde(deserialize_with = "string_or_f64")]
+    pub close: f64,
+    #[serde(deserialize_with = "string_or_f64")]
+    pub vwap: f64,
+    #[serde(deserialize_with = "string_or_f64")]
+    pub volume: f64,
+    pub count: u64,
+}
+
+impl Request for OhlcRequest {
+    const SIGNED: bool = false;
+    const ENDPOINT: &'static str = "/0/public/OHLC";
+    const HAS_PAYLOAD: bool = true;
+    type Response = OhlcResponse;
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct DepthRequest {
+    pub pair: String,
+    // Levels per side, 100 if not set
+    pub count: Option<u32>,
+}
+
+impl DepthRequest {
+    pub fn new(pair: &str) -> Self {
+        DepthRequest { pair: pair.into(), count: None }
+    }
+}
+
+// Keyed by the pair name, e.g. "XXBTZUSD"
+pub type DepthResponse = HashMap<String, Depth>;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Depth {
+    pub asks: Vec<DepthLevel>,
+    pub bids: Vec<DepthLevel>,
+}
+
+// [price, volume, timestamp]
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct DepthLevel {
+    #[serde(deserialize_with = "string_or_f64")]
+    pub price: f64,
+    #[serde(deserialize_with = "string_or_f64")]
+    pub volume: f64,
+    // Unix timestamp of the last update
+    pub timestamp: i64,
+}
+
+impl Request for DepthRequest {
+    const SIGNED: bool = false;
+    const ENDPOINT: &'static str = "/0/public/Depth";
+    const HAS_PAYLOAD: bool = true;
+    type Response = DepthResponse;
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct TradesRequest {
+    pub pair: String,
+    // Only trades after this cursor, e.g. the `last` of a previous response
+    pub since: Option<String>,
+    // At most 1000, the default
+    pub count: Option<u32>,
+}
+
+impl TradesRequest {
+    pub fn new(pair: &str) -> Self {
+        TradesRequest {
+            pair: pair.into(),
+            since: None,
+            count: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TradesResponse {
+    pub pair: String,
+    pub trades: Vec<Trade>,
+    pub last: String,
+}
+
+impl<'de> Deserialize<'de> for TradesResponse {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let Keyed { pair, data, last } = Keyed::deserialize(deserializer)?;
+        Ok(TradesResponse { pair, trades: data, last })
+    }
+}
+
+// [price, volume, time, side, order type, miscellaneous, trade id]
+#[derive(Deserialize, Debug, Clone)]
+pub struct Trade {
+    #[serde(deserialize_with = "string_or_f64")]
+    pub price: f64,
+    #[serde(deserialize_with = "string_or_f64")]
+    pub volume: f64,
+    // Unix timestamp with sub-second precision
+    pub time: f64,
+    pub side: Side,
+    pub order_type: TradeOrderType,
+    pub misc: String,
+    // Only sent by newer API versions
+    #[serde(default)]
+    pub trade_id: Option<u64>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TradeOrderType {
+    #[serde(rename = "m")]
+    Market,
+    #[serde(rename = "l")]
+    Limit,
+}
+
+impl Request for TradesRequest {
+    const SIGNED: bool = false;
+    const ENDPOINT: &'static str = "/0/public/Trades";
+    const HAS_PAYLOAD: bool = true;
+    type Response = TradesResponse;
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct SpreadRequest {
+    pub pair: String,
+    // Only spreads after this unix timestamp, e.g. the `last` of a previous response
+    pub since: Option<i64>,
+}
+
+impl SpreadRequest {
+    pub fn new(pair: &str) -> Self {
+        SpreadRequest { pair: pair.into(), since: None }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SpreadResponse {
+    pub pair: String,
+    pub spreads: Vec<Spread>,
+    pub last: i64,
+}
+
+impl<'de> Deserialize<'de> for SpreadResponse {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let Keyed { pair, data, last } = Keyed::deserialize(deserializer)?;
+        Ok(SpreadResponse { pair, spreads: data, last })
+    }
+}
+
+// [time, bid, ask]
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct Spread {
+    pub time: i64,
+    #[serde(deserialize_with = "string_or_f64")]
+    pub bid: f64,
+    #[serde(deserialize_with = "string_or_f64")]
+    pub ask: f64,
+}
+
+impl Request for SpreadRequest {
+    const SIGNED: bool = false;
+    const ENDPOINT: &'static str = "/0/public/Spread";
+    const HAS_PAYLOAD: bool = true;
+    type Response = SpreadResponse;
+}
+
+// OHLC, Trades and Spread return the data under the pair name, next to a `last` cursor
+struct Keyed<T, L> {
+    pair: String,
+    data: T,
+    last: L,
+}
+
+impl<'de, T, L> Deserialize<'de> for Keyed<T, L>
+where
+    T: Deserialize<'de>,
+    L: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct KeyedVisitor<T, L>(PhantomData<(T, L)>);
+
+        impl<'de, T, L> Visitor<'de> for KeyedVisitor<T, L>
+        where
+            T: Deserialize<'de>,
+            L: Deserialize<'de>,
+        {
+            type Value = Keyed<T, L>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map with a pair name and last")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut pair = None;
+                let mut last = None;
+
+                while let Some(key) = map.next_key::<String>()? {
+                    if key == "last" {
+                        last = Some(map.next_value()?);
+                    } else {
+                        pair = Some((key, map.next_value()?));
+                    }
+                }
+
+                let (pair, data) = pair.ok_or_else(|| A::Error::custom("missing pair"))?;
+                let last = last.ok_or_else(|| A::Error::missing_field("last"))?;
+                Ok(Keyed { pair, data, last })
+            }
+        }
+
+        deserializer.deserialize_map(KeyedVisitor(PhantomData))
+    }
+}
+
 pub fn deserialize<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
 where
     D: Deserializer<'de>,
@@ -0,0 +1,7 @@
+use kraken_core::Millis;
+
+pub use kraken_core::NonceSource;
+
+// Kraken spot takes nonces in milliseconds
+pub type MonotonicNonce = kraken_core::MonotonicNonce<Millis>;
+pub type PersistentNonce = kraken_core::PersistentNonce<Millis>;
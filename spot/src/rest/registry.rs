@@ -0,0 +1,67 @@
+use super::models::{Asset, AssetPair, AssetPairsResponse, AssetsResponse};
+use std::collections::HashMap;
+
+// Resolves the names Kraken uses for the same asset or pair in different places: the REST name
+// ("XXBTZUSD", "XXBT"), the alternative name ("XBTUSD", "XBT") and the websocket name ("XBT/USD").
+// Build it from the Assets and AssetPairs responses.
+#[derive(Debug, Clone)]
+pub struct Registry {
+    assets: HashMap<String, Asset>,
+    pairs: HashMap<String, AssetPair>,
+    // Every known name to the REST name
+    asset_names: HashMap<String, String>,
+    pair_names: HashMap<String, String>,
+}
+
+impl Registry {
+    pub fn new(assets: AssetsResponse, pairs: AssetPairsResponse) -> Self {
+        let mut asset_names = HashMap::new();
+        for (name, asset) in &assets {
+            asset_names.insert(asset.altname.clone(), name.clone());
+            asset_names.insert(name.clone(), name.clone());
+        }
+
+        let mut pair_names = HashMap::new();
+        for (name, pair) in &pairs {
+            if let Some(wsname) = &pair.wsname {
+                pair_names.insert(wsname.clone(), name.clone());
+            }
+            pair_names.insert(pair.altname.clone(), name.clone());
+            pair_names.insert(name.clone(), name.clone());
+        }
+
+        Registry {
+            assets,
+            pairs,
+            asset_names,
+            pair_names,
+        }
+    }
+
+    // Accepts any form, e.g. "XXBT" or "XBT", and returns the REST name with the asset
+    pub fn asset(&self, name: &str) -> Option<(&str, &Asset)> {
+        let name = self.asset_names.get(name)?;
+        Some((name.as_str(), &self.assets[name]))
+    }
+
+    // Accepts any form, e.g. "XXBTZUSD", "XBTUSD" or "XBT/USD", and returns the REST name with the pair
+    pub fn pair(&self, name: &str) -> Option<(&str, &AssetPair)> {
+        let name = self.pair_names.get(name)?;
+        Some((name.as_str(), &self.pairs[name]))
+    }
+
+    // "XXBTZUSD"
+    pub fn pair_name(&self, name: &str) -> Option<&str> {
+        self.pair(name).map(|(name, _)| name)
+    }
+
+    // "XBTUSD"
+    pub fn altname(&self, name: &str) -> Option<&str> {
+        self.pair(name).map(|(_, pair)| pair.altname.as_str())
+    }
+
+    // "XBT/USD"
+    pub fn wsname(&self, name: &str) -> Option<&str> {
+        self.pair(name).and_then(|(_, pair)| pair.wsname.as_deref())
+    }
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use chrono::{DateTime, Utc};
+use common::FakeTransport;
+use kraken_spot::errors::KrakenError;
+use kraken_spot::rest::{BalanceRequest, HttpRequest, HttpResponse, Kraken, Middleware};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_sync_time() {
+    let transport = FakeTransport::new(r#"{"error":[],"result":{"unixtime":4102444800,"rfc1123":"Fri, 01 Jan 2100 00:00:00 +0000"}}"#);
+    let client = transport.client();
+
+    let offset = client.sync_time().await.unwrap();
+
+    // The second-precision server time is taken as the middle of its second
+    let server: DateTime<Utc> = "2100-01-01T00:00:00.500Z".parse().unwrap();
+    assert!((client.server_clock().now() - server).num_seconds().abs() < 5);
+    assert_eq!(offset, client.server_clock().offset());
+    assert_eq!(transport.sent()[0].url.path(), "/0/public/Time");
+}
+
+#[derive(Clone, Default)]
+struct Recorder {
+    events: Arc<Mutex<Vec<String>>>,
+}
+
+impl Middleware<KrakenError> for Recorder {
+    fn on_request(&self, endpoint: &str, req: &HttpRequest) {
+        self.events.lock().unwrap().push(format!("request {} {}", endpoint, header(req, "API-Key")));
+    }
+
+    fn on_response(&self, endpoint: &str, req: &HttpRequest, resp: Result<&HttpResponse, &KrakenError>, _latency: Duration) {
+        let status = resp.unwrap().status.as_u16();
+        self.events.lock().unwrap().push(format!("response {} {} {}", endpoint, header(req, "API-Sign"), status));
+    }
+}
+
+fn header(req: &HttpRequest, name: &str) -> String {
+    req.headers.iter().find(|(n, _)| n == name).map(|(_, v)| v.clone()).unwrap_or_default()
+}
+
+#[tokio::test]
+async fn test_middleware() {
+    let transport = FakeTransport::new(r#"{"error":[],"result":{"ZUSD":"1.0"}}"#);
+    let recorder = Recorder::default();
+    let client = Kraken::builder()
+        .transport(Arc::new(transport.clone()))
+        .credential(Some(("key".into(), "c2VjcmV0".into())))
+        .middlewares(vec![Arc::new(recorder.clone())])
+        .build()
+        .unwrap();
+
+    client.request(BalanceRequest).await.unwrap();
+
+    assert_eq!(
+        *recorder.events.lock().unwrap(),
+        vec![
+            "request /0/private/Balance <redacted>".to_string(),
+            "response /0/private/Balance <redacted> 200".to_string()
+        ]
+    );
+    // Only the copy handed to middlewares is redacted
+    assert_eq!(header(&transport.sent()[0], "API-Key"), "key");
+}
@@ -0,0 +1,61 @@
+// Shared by the offline tests, each of which uses a different part of it
+#![allow(dead_code)]
+
+use futures::future::{ready, BoxFuture, FutureExt};
+use http::StatusCode;
+use kraken_spot::errors::KrakenError;
+use kraken_spot::rest::{HttpRequest, HttpResponse, Kraken, Transport};
+use std::sync::{Arc, Mutex};
+
+// Answers requests with canned bodies in turn, repeating the last one, and remembers what was sent
+#[derive(Clone)]
+pub struct FakeTransport {
+    bodies: Vec<String>,
+    sent: Arc<Mutex<Vec<HttpRequest>>>,
+}
+
+impl FakeTransport {
+    pub fn new(body: &str) -> Self {
+        Self::pages(vec![body])
+    }
+
+    pub fn pages<S: ToString>(bodies: Vec<S>) -> Self {
+        FakeTransport {
+            bodies: bodies.iter().map(|b| b.to_string()).collect(),
+            sent: Arc::new(Mutex::new(vec![])),
+        }
+    }
+
+    pub fn client(&self) -> Kraken {
+        Kraken::builder()
+            .transport(Arc::new(self.clone()))
+            .credential(Some(("key".into(), "c2VjcmV0".into())))
+            .build()
+            .unwrap()
+    }
+
+    pub fn sent(&self) -> Vec<HttpRequest> {
+        self.sent.lock().unwrap().clone()
+    }
+
+    pub fn count(&self) -> usize {
+        self.sent.lock().unwrap().len()
+    }
+
+    pub fn body(&self) -> String {
+        self.sent.lock().unwrap().last().unwrap().body.clone()
+    }
+
+    pub fn bodies(&self) -> Vec<String> {
+        self.sent.lock().unwrap().iter().map(|r| r.body.clone()).collect()
+    }
+}
+
+impl Transport<KrakenError> for FakeTransport {
+    fn send(&self, req: HttpRequest) -> BoxFuture<'static, Result<HttpResponse, KrakenError>> {
+        let mut sent = self.sent.lock().unwrap();
+        let body = self.bodies[sent.len().min(self.bodies.len() - 1)].clone();
+        sent.push(req);
+        ready(Ok(HttpResponse { status: StatusCode::OK, body })).boxed()
+    }
+}
@@ -0,0 +1,598 @@
+mod common;
+
+use common::FakeTransport;
+use futures::stream::TryStreamExt;
+use kraken_spot::errors::KrakenError;
+use kraken_spot::rest::{AddOrderBatchRequest, AddOrderRequest, EditOrderRequest, EditStatus};
+use kraken_spot::rest::{AssetPairsRequest, AssetsRequest, BalanceExRequest, BalanceRequest, FeeTier, GetTradeBalanceRequest, Registry, TradeVolumeRequest};
+use kraken_spot::rest::{CancelAllOrdersAfterRequest, CancelAllRequest, CancelOrderRequest};
+use kraken_spot::rest::{CloseTime, ClosedOrdersRequest, OpenOrdersRequest, QueryOrdersRequest};
+use kraken_spot::rest::{DepthRequest, OhlcRequest, SpreadRequest, SystemStatus, SystemStatusRequest, TickerRequest, TradeOrderType, TradesRequest};
+use kraken_spot::rest::{LedgerType, LedgersRequest, QueryLedgersRequest, QueryTradesRequest, TradeFilter, TradesHistoryRequest};
+use kraken_spot::{Currency, OrderFlag, OrderStatus, OrderTime, OrderType, Price, Side, TimeInForce, Trigger};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_trade_balance() {
+    let transport = FakeTransport::new(
+        r#"{"error":[],"result":{"eb":"1101.3425","tb":"392.2264","m":"7.0354","n":"-10.0232","c":"21.1063","v":"31.1297","e":"382.2032","mf":"375.1678","ml":"5432.57","uv":"0.0000"}}"#,
+    );
+
+    let resp = transport.client().request(GetTradeBalanceRequest::default()).await.unwrap();
+    assert_eq!(resp.eb, 1101.3425);
+    assert_eq!(resp.n, -10.0232);
+    assert_eq!(resp.mf, 375.1678);
+    assert_eq!(resp.ml, Some(5432.57));
+    assert!(transport.body().starts_with("nonce="));
+
+    let req = GetTradeBalanceRequest {
+        asset: Some("XXBT".into()),
+        ..GetTradeBalanceRequest::default()
+    };
+    transport.client().request(req).await.unwrap();
+    assert!(transport.body().starts_with("asset=XXBT&nonce="));
+}
+
+#[tokio::test]
+async fn test_trade_balance_without_positions() {
+    let transport = FakeTransport::new(r#"{"error":[],"result":{"eb":"0.0","tb":"0.0","m":"0.0","n":"0.0","c":"0.0","v":"0.0","e":"0.0","mf":"0.0"}}"#);
+
+    let resp = transport.client().request(GetTradeBalanceRequest::default()).await.unwrap();
+    assert_eq!(resp.ml, None);
+}
+
+const ASSETS: &str = r#"{"error":[],"result":{
+    "XXBT":{"aclass":"currency","altname":"XBT","decimals":10,"display_decimals":5,"collateral_value":1.0,"status":"enabled"},
+    "ZUSD":{"aclass":"currency","altname":"USD","decimals":4,"display_decimals":2,"status":"enabled"}
+}}"#;
+
+const ASSET_PAIRS: &str = r#"{"error":[],"result":{
+    "XXBTZUSD":{"altname":"XBTUSD","wsname":"XBT/USD","aclass_base":"currency","base":"XXBT","aclass_quote":"currency","quote":"ZUSD","lot":"unit",
+        "cost_decimals":5,"pair_decimals":1,"lot_decimals":8,"lot_multiplier":1,"leverage_buy":[2,3,4,5],"leverage_sell":[2,3,4,5],
+        "fees":[[0,0.26],[50000,0.24]],"fees_maker":[[0,0.16],[50000,0.14]],"fee_volume_currency":"ZUSD","margin_call":80,"margin_stop":40,
+        "ordermin":"0.0001","costmin":"0.5","tick_size":"0.1","status":"online"},
+    "XXBTZUSD.d":{"altname":"XBTUSD.d","aclass_base":"currency","base":"XXBT","aclass_quote":"currency","quote":"ZUSD","lot":"unit",
+        "pair_decimals":1,"lot_decimals":8,"lot_multiplier":1,"leverage_buy":[],"leverage_sell":[],
+        "fees":[[0,0.26]],"fee_volume_currency":"ZUSD","margin_call":80,"margin_stop":40}
+}}"#;
+
+#[tokio::test]
+async fn test_system_status() {
+    let transport = FakeTransport::new(r#"{"error":[],"result":{"status":"cancel_only","timestamp":"2020-09-01T10:00:00Z"}}"#);
+
+    let resp = transport.client().request(SystemStatusRequest).await.unwrap();
+    assert_eq!(resp.status, SystemStatus::CancelOnly);
+    assert_eq!(transport.body(), "");
+}
+
+#[tokio::test]
+async fn test_asset_pairs() {
+    let transport = FakeTransport::new(ASSET_PAIRS);
+
+    let req = AssetPairsRequest { pair: Some("XBTUSD".into()) };
+    let resp = transport.client().request(req).await.unwrap();
+    // Public requests are not signed and take no nonce
+    assert_eq!(transport.body(), "pair=XBTUSD");
+
+    let pair = &resp["XXBTZUSD"];
+    assert_eq!(pair.order_min, Some(0.0001));
+    assert_eq!(pair.tick_size, Some(0.1));
+    assert_eq!(pair.leverage_buy, vec![2, 3, 4, 5]);
+    assert_eq!(pair.fees_maker[1], FeeTier { volume: 50000., fee: 0.14 });
+    assert_eq!(resp["XXBTZUSD.d"].wsname, None);
+}
+
+#[tokio::test]
+async fn test_registry() {
+    let assets = FakeTransport::new(ASSETS).client().request(AssetsRequest::default()).await.unwrap();
+    let pairs = FakeTransport::new(ASSET_PAIRS).client().request(AssetPairsRequest::default()).await.unwrap();
+    let registry = Registry::new(assets, pairs);
+
+    for name in &["XXBTZUSD", "XBTUSD", "XBT/USD"] {
+        assert_eq!(registry.pair_name(name), Some("XXBTZUSD"));
+        assert_eq!(registry.altname(name), Some("XBTUSD"));
+        assert_eq!(registry.wsname(name), Some("XBT/USD"));
+    }
+    assert_eq!(registry.wsname("XBTUSD.d"), None);
+    assert_eq!(registry.pair_name("ETHUSD"), None);
+
+    assert_eq!(registry.asset("XBT").unwrap().0, "XXBT");
+    assert_eq!(registry.asset("ZUSD").unwrap().1.altname, "USD");
+}
+
+#[tokio::test]
+async fn test_ticker() {
+    let transport = FakeTransport::new(
+        r#"{"error":[],"result":{"XXBTZUSD":{"a":["30300.10000","1","1.000"],"b":["30300.00000","1","1.000"],"c":["30303.20000","0.00067643"],
+        "v":["4083.67001100","4412.73601799"],"p":["30706.77771","30689.13205"],"t":[34619,38907],"l":["29868.30000","29868.30000"],
+        "h":["31631.00000","31631.00000"],"o":"30502.80000"}}}"#,
+    );
+
+    let resp = transport.client().request(TickerRequest::default()).await.unwrap();
+    let ticker = &resp["XXBTZUSD"];
+    assert_eq!(ticker.ask.price, 30300.1);
+    assert_eq!(ticker.last.lot_volume, 0.00067643);
+    assert_eq!(ticker.vwap.last_24h, 30689.13205);
+    assert_eq!(ticker.trades.today, 34619);
+    assert_eq!(ticker.open, 30502.8);
+}
+
+#[tokio::test]
+async fn test_ohlc() {
+    let transport = FakeTransport::new(r#"{"error":[],"result":{"XXBTZUSD":[[1688671200,"30306.1","30306.2","30305.7","30305.7","30306.1","3.39243896",23]],"last":1688672160}}"#);
+
+    let req = OhlcRequest {
+        interval: Some(60),
+        ..OhlcRequest::new("XBTUSD")
+    };
+    let resp = transport.client().request(req).await.unwrap();
+    assert_eq!(transport.body(), "interval=60&pair=XBTUSD");
+    assert_eq!(resp.pair, "XXBTZUSD");
+    assert_eq!(resp.last, 1688672160);
+    assert_eq!(resp.candles[0].high, 30306.2);
+    assert_eq!(resp.candles[0].count, 23);
+}
+
+#[tokio::test]
+async fn test_depth() {
+    let transport = FakeTransport::new(
+        r#"{"error":[],"result":{"XXBTZUSD":{"asks":[["30384.10000","2.059",1688671659]],"bids":[["30297.00000","0.115",1688671656],["30296.70000","0.002",1688671674]]}}}"#,
+    );
+
+    let resp = transport.client().request(DepthRequest::new("XBTUSD")).await.unwrap();
+    let depth = &resp["XXBTZUSD"];
+    assert_eq!(depth.asks[0].volume, 2.059);
+    assert_eq!(depth.bids[1].price, 30296.7);
+    assert_eq!(depth.bids[1].timestamp, 1688671674);
+}
+
+#[tokio::test]
+async fn test_trades() {
+    let transport = FakeTransport::new(
+        r#"{"error":[],"result":{"XXBTZUSD":[["30243.40000","0.34507674",1688669597.8277369,"b","m","",61044952],["30243.30000","0.00376960",1688669598.2804112,"s","l",""]],
+        "last":"1688671969993150842"}}"#,
+    );
+
+    let resp = transport.client().request(TradesRequest::new("XBTUSD")).await.unwrap();
+    assert_eq!(resp.last, "1688671969993150842");
+    assert_eq!(resp.trades[0].side, Side::Buy);
+    assert_eq!(resp.trades[0].order_type, TradeOrderType::Market);
+    assert_eq!(resp.trades[0].trade_id, Some(61044952));
+    assert_eq!(resp.trades[1].side, Side::Sell);
+    assert_eq!(resp.trades[1].trade_id, None);
+}
+
+#[tokio::test]
+async fn test_spread() {
+    let transport = FakeTransport::new(r#"{"error":[],"result":{"XXBTZUSD":[[1688671834,"30292.10000","30297.50000"]],"last":1688672106}}"#);
+
+    let req = SpreadRequest {
+        since: Some(1688671800),
+        ..SpreadRequest::new("XBTUSD")
+    };
+    let resp = transport.client().request(req).await.unwrap();
+    assert_eq!(transport.body(), "pair=XBTUSD&since=1688671800");
+    assert_eq!(resp.last, 1688672106);
+    assert_eq!(resp.spreads[0].ask, 30297.5);
+}
+
+fn currency(s: &str) -> Currency {
+    s.parse().unwrap()
+}
+
+#[test]
+fn test_currency() {
+    assert_eq!(currency("XXBT"), currency("XBT"));
+    assert_eq!(currency("ZUSD").to_string(), "USD");
+    assert_eq!(currency("usdt").to_string(), "USDT");
+    assert_eq!(currency("dot.s").base(), currency("DOT"));
+    assert_ne!(currency("XBT.M"), currency("XBT"));
+}
+
+#[tokio::test]
+async fn test_balance() {
+    let transport = FakeTransport::new(r#"{"error":[],"result":{"ZUSD":"171288.6158","XXBT":"0.0011000000","DOT.S":"12.5"}}"#);
+
+    let resp = transport.client().request(BalanceRequest).await.unwrap();
+    assert_eq!(resp.balances[&currency("USD")], 171288.6158);
+    assert_eq!(resp.balances[&currency("XBT")], 0.0011);
+    assert_eq!(resp.balances[&currency("DOT.S")], 12.5);
+}
+
+#[tokio::test]
+async fn test_balance_ex() {
+    let transport = FakeTransport::new(
+        r#"{"error":[],"result":{"ZUSD":{"balance":"25435.21","hold_trade":"8249.76"},"XXBT":{"balance":"1.2","credit":"0.5","credit_used":"0.1","hold_trade":"0.3"}}}"#,
+    );
+
+    let resp = transport.client().request(BalanceExRequest).await.unwrap();
+    assert_eq!(resp[&currency("USD")].hold_trade, 8249.76);
+    assert_eq!(resp[&currency("USD")].credit, None);
+    assert!((resp[&currency("XBT")].available() - 1.3).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn test_trade_volume() {
+    let transport = FakeTransport::new(
+        r#"{"error":[],"result":{"currency":"ZUSD","volume":"200709587.4223",
+        "fees":{"XXBTZUSD":{"fee":"0.1000","minfee":"0.1000","maxfee":"0.2600","nextfee":null,"nextvolume":null,"tiervolume":"10000000.0000"}},
+        "fees_maker":{"XXBTZUSD":{"fee":"0.0000","minfee":"0.0000","maxfee":"0.1600","nextfee":null,"nextvolume":null,"tiervolume":"10000000.0000"}}}}"#,
+    );
+
+    let req = TradeVolumeRequest { pair: Some("XBTUSD".into()) };
+    let resp = transport.client().request(req).await.unwrap();
+    assert!(transport.body().starts_with("pair=XBTUSD&nonce="));
+    assert_eq!(resp.currency, currency("USD"));
+    assert_eq!(resp.fees["XXBTZUSD"].max_fee, 0.26);
+    assert_eq!(resp.fees_maker["XXBTZUSD"].next_fee, None);
+    assert_eq!(resp.fees_maker["XXBTZUSD"].tier_volume, Some(10000000.));
+}
+
+fn params(body: &str) -> &str {
+    &body[..body.find("&nonce=").unwrap()]
+}
+
+#[tokio::test]
+async fn test_add_order() {
+    let transport = FakeTransport::new(r#"{"error":[],"result":{"descr":{"order":"buy 1.25000000 XBTUSD @ limit 27500.0"},"txid":["OU22CG-KLAF2-FWUDD7"]}}"#);
+
+    let req = AddOrderRequest::limit("XBTUSD", Side::Buy, Price::fixed(27500.), 1.25)
+        .flag(OrderFlag::Post)
+        .flag(OrderFlag::Fciq)
+        .flag(OrderFlag::Post)
+        .time_in_force(TimeInForce::GTD)
+        .expire_time(OrderTime::After(Duration::from_secs(60)))
+        .userref(7)
+        .cl_ord_id("my-order");
+    let resp = transport.client().request(req).await.unwrap();
+    assert_eq!(
+        params(&transport.body()),
+        "cl_ord_id=my-order&expiretm=%2B60&oflags=post%2Cfciq&ordertype=limit&pair=XBTUSD&price=27500&timeinforce=GTD&type=buy&userref=7&volume=1.25"
+    );
+    assert_eq!(resp.txid, vec!["OU22CG-KLAF2-FWUDD7".to_string()]);
+}
+
+#[tokio::test]
+async fn test_add_order_prices() {
+    let transport = FakeTransport::new(r#"{"error":[],"result":{"descr":{"order":"sell 0.50000000 XBTUSD @ trailing stop +1.0000%"}}}"#);
+
+    let req = AddOrderRequest::trailing_stop_limit("XBTUSD", Side::Sell, Price::plus(1.).percent(), Price::minus(0.5), 0.5)
+        .trigger(Trigger::Index)
+        .validate(true);
+    let resp = transport.client().request(req).await.unwrap();
+    assert_eq!(
+        params(&transport.body()),
+        "ordertype=trailing-stop-limit&pair=XBTUSD&price=%2B1%25&price2=-0.5&trigger=index&type=sell&validate=true&volume=0.5"
+    );
+    assert!(resp.txid.is_empty());
+
+    let req = AddOrderRequest::stop_loss("XBTUSD", Side::Sell, Price::auto(100.), 2.)
+        .leverage(5)
+        .reduce_only(true)
+        .start_time(OrderTime::Now);
+    transport.client().request(req).await.unwrap();
+    assert_eq!(
+        params(&transport.body()),
+        "leverage=5&ordertype=stop-loss&pair=XBTUSD&price=%23100&reduce_only=true&starttm=0&type=sell&volume=2.0"
+    );
+}
+
+#[tokio::test]
+async fn test_add_order_batch() {
+    let transport = FakeTransport::new(
+        r#"{"error":[],"result":{"orders":[{"descr":{"order":"buy 0.10000000 XBTUSD @ limit 27000.0"},"txid":"OU22CG-KLAF2-FWUDD7"},{"error":"EOrder:Insufficient funds"}]}}"#,
+    );
+
+    let orders = vec![
+        AddOrderRequest::limit("XBTUSD", Side::Buy, Price::fixed(27000.), 0.1).flag(OrderFlag::Post),
+        AddOrderRequest::limit("XBTUSD", Side::Buy, Price::fixed(26000.), 0.2).userref(2),
+    ];
+    let req = AddOrderBatchRequest::new(orders).unwrap().validate(true);
+    let resp = transport.client().request(req.clone()).await.unwrap();
+
+    let body: Value = serde_json::from_str(&transport.body()).unwrap();
+    assert!(body["nonce"].is_u64());
+    assert_eq!(body["pair"], "XBTUSD");
+    assert_eq!(body["validate"], true);
+    assert_eq!(
+        body["orders"],
+        json!([
+            {"ordertype": "limit", "type": "buy", "price": "27000", "volume": 0.1, "oflags": "post"},
+            {"ordertype": "limit", "type": "buy", "price": "26000", "volume": 0.2, "userref": 2},
+        ])
+    );
+
+    assert_eq!(resp.orders.len(), req.orders().len());
+    assert_eq!(resp.orders[0].txid.as_deref(), Some("OU22CG-KLAF2-FWUDD7"));
+    assert_eq!(resp.orders[1].error.as_deref(), Some("EOrder:Insufficient funds"));
+}
+
+#[test]
+fn test_invalid_order_batch() {
+    assert!(AddOrderBatchRequest::new(vec![]).is_err());
+
+    let mixed = vec![AddOrderRequest::market("XBTUSD", Side::Buy, 0.1), AddOrderRequest::market("ETHUSD", Side::Buy, 0.1)];
+    assert!(AddOrderBatchRequest::new(mixed).is_err());
+
+    let many = vec![AddOrderRequest::market("XBTUSD", Side::Buy, 0.1); 16];
+    assert!(AddOrderBatchRequest::new(many).is_err());
+}
+
+#[tokio::test]
+async fn test_edit_order() {
+    let transport = FakeTransport::new(
+        r#"{"error":[],"result":{"status":"ok","txid":"OFVXHJ-KPQ3B-VS7ELA","originaltxid":"OHYO67-6LP66-HMQ437","volume":"0.00030000","price":"19500.0","price2":"32500.0","orders_cancelled":1,"descr":{"order":"buy 0.00030000 XXBTZGBP @ limit 19500.0"}}}"#,
+    );
+
+    let order = AddOrderRequest::limit("XBTUSD", Side::Buy, Price::fixed(19500.), 0.0003).flag(OrderFlag::Post);
+    let resp = transport.client().request(EditOrderRequest::replace("OHYO67-6LP66-HMQ437", &order)).await.unwrap();
+    assert_eq!(params(&transport.body()), "oflags=post&pair=XBTUSD&price=19500&txid=OHYO67-6LP66-HMQ437&volume=0.0003");
+    assert_eq!(resp.status, EditStatus::Ok);
+    assert_eq!(resp.txid.as_deref(), Some("OFVXHJ-KPQ3B-VS7ELA"));
+    assert_eq!(resp.price, Some(19500.));
+
+    let req = EditOrderRequest::from_userref(7, "XBTUSD").price(Price::plus(10.));
+    transport.client().request(req).await.unwrap();
+    assert_eq!(params(&transport.body()), "pair=XBTUSD&price=%2B10&txid=7");
+}
+
+#[tokio::test]
+async fn test_cancel_order() {
+    let transport = FakeTransport::new(r#"{"error":[],"result":{"count":1}}"#);
+
+    let resp = transport.client().request(CancelOrderRequest::from_txid("OYVGEW-VYV5B-UUEXSK")).await.unwrap();
+    assert_eq!(params(&transport.body()), "txid=OYVGEW-VYV5B-UUEXSK");
+    assert_eq!(resp.count, 1);
+
+    transport.client().request(CancelOrderRequest::from_userref(7)).await.unwrap();
+    assert_eq!(params(&transport.body()), "txid=7");
+
+    transport.client().request(CancelOrderRequest::from_cl_ord_id("my-order")).await.unwrap();
+    assert_eq!(params(&transport.body()), "cl_ord_id=my-order");
+
+    let resp = transport.client().request(CancelAllRequest).await.unwrap();
+    assert_eq!(resp.count, 1);
+}
+
+#[tokio::test]
+async fn test_cancel_all_orders_after() {
+    let transport = FakeTransport::new(r#"{"error":[],"result":{"currentTime":"2023-03-24T17:41:56Z","triggerTime":"2023-03-24T17:42:56Z"}}"#);
+    let resp = transport.client().request(CancelAllOrdersAfterRequest::new(Duration::from_secs(60))).await.unwrap();
+    assert_eq!(params(&transport.body()), "timeout=60");
+    assert_eq!(resp.trigger_time.unwrap().to_rfc3339(), "2023-03-24T17:42:56+00:00");
+
+    // Sub-second timeouts round up instead of disarming the switch
+    transport.client().request(CancelAllOrdersAfterRequest::new(Duration::from_millis(500))).await.unwrap();
+    assert_eq!(params(&transport.body()), "timeout=1");
+    transport.client().request(CancelAllOrdersAfterRequest::new(Duration::from_millis(60_001))).await.unwrap();
+    assert_eq!(params(&transport.body()), "timeout=61");
+
+    let transport = FakeTransport::new(r#"{"error":[],"result":{"currentTime":"2023-03-24T17:41:56Z","triggerTime":"0"}}"#);
+    let resp = transport.client().request(CancelAllOrdersAfterRequest::disarm()).await.unwrap();
+    assert_eq!(params(&transport.body()), "timeout=0");
+    assert_eq!(resp.trigger_time, None);
+}
+
+#[tokio::test]
+async fn test_heartbeat() {
+    let transport = FakeTransport::new(r#"{"error":[],"result":{"currentTime":"2023-03-24T17:41:56Z","triggerTime":"2023-03-24T17:42:56Z"}}"#);
+    let heartbeat = transport.client().heartbeat(Duration::from_secs(60), Duration::from_millis(50)).unwrap();
+
+    assert!(tokio::time::timeout(Duration::from_millis(120), heartbeat).await.is_err());
+    assert!(transport.count() >= 2);
+    assert_eq!(params(&transport.body()), "timeout=60");
+}
+
+#[test]
+fn test_heartbeat_interval_below_timeout() {
+    let client = FakeTransport::new("").client();
+
+    for interval in [Duration::from_secs(60), Duration::from_secs(61)].iter() {
+        match client.heartbeat(Duration::from_secs(60), *interval) {
+            Err(KrakenError::InvalidHeartbeat(_)) => {}
+            r => panic!("unexpected {:?}", r.map(|_| ())),
+        }
+    }
+}
+
+// A response listing `rows` by id under `key`, with Kraken's total `count` if the endpoint sends one
+fn page(key: &str, rows: &[(&str, &str)], count: Option<u32>) -> String {
+    let rows: Vec<_> = rows.iter().map(|(id, row)| format!(r#""{}":{}"#, id, row)).collect();
+    let count = count.map(|c| format!(r#","count":{}"#, c)).unwrap_or_default();
+    format!(r#"{{"error":[],"result":{{"{}":{{{}}}{}}}}}"#, key, rows.join(","), count)
+}
+
+const ORDER: &str = r#"{"refid":null,"userref":0,"status":"closed","reason":null,"opentm":1688665496.7808,"closetm":1688665499.1922,"starttm":0,"expiretm":0,
+    "descr":{"pair":"XBTUSD","type":"buy","ordertype":"stop-loss-limit","price":"27500.0","price2":"27400.0","leverage":"none","order":"buy 1.25 XBTUSD @ stop loss 27500.0 -> limit 27400.0","close":""},
+    "vol":"1.25000000","vol_exec":"1.25000000","cost":"34375.0","fee":"89.375","price":"27500.0","stopprice":"27500.0","limitprice":"27400.0","misc":"stopped,touched","oflags":"fciq,post","trades":["TZX2WP-XSEOP-FP7WYR"]}"#;
+
+#[tokio::test]
+async fn test_query_orders() {
+    let transport = FakeTransport::new(&format!(r#"{{"error":[],"result":{{"OBCMZD-JIEE7-77TH3F":{}}}}}"#, ORDER));
+
+    let req = QueryOrdersRequest {
+        trades: Some(true),
+        ..QueryOrdersRequest::new(&["OBCMZD-JIEE7-77TH3F", "OMMDB2-FSB6Z-7W3HPO"]).unwrap()
+    };
+    let resp = transport.client().request(req).await.unwrap();
+    assert_eq!(params(&transport.body()), "trades=true&txid=OBCMZD-JIEE7-77TH3F%2COMMDB2-FSB6Z-7W3HPO");
+
+    let order = &resp["OBCMZD-JIEE7-77TH3F"];
+    assert_eq!(order.status, OrderStatus::Closed);
+    assert_eq!(order.descr.side, Side::Buy);
+    assert_eq!(order.descr.order_type, OrderType::StopLossLimit);
+    assert_eq!(order.descr.price2, 27400.);
+    assert_eq!(order.vol_exec, 1.25);
+    assert_eq!(order.avg_price, 27500.);
+    assert_eq!(order.close_time, Some(1688665499.1922));
+    assert_eq!(order.misc, vec!["stopped", "touched"]);
+    assert_eq!(order.oflags, vec!["fciq", "post"]);
+    assert_eq!(order.trades, vec!["TZX2WP-XSEOP-FP7WYR"]);
+}
+
+#[test]
+fn test_query_orders_txid_limit() {
+    let txids: Vec<_> = (0..=QueryOrdersRequest::MAX_TXIDS).map(|i| format!("O{}", i)).collect();
+
+    assert!(QueryOrdersRequest::new(&txids[..QueryOrdersRequest::MAX_TXIDS]).is_ok());
+    assert!(matches!(QueryOrdersRequest::new(&txids), Err(KrakenError::InvalidIdList(_))));
+    assert!(matches!(QueryOrdersRequest::new::<&str>(&[]), Err(KrakenError::InvalidIdList(_))));
+}
+
+#[tokio::test]
+async fn test_open_orders() {
+    let transport = FakeTransport::new(
+        r#"{"error":[],"result":{"open":{"OQCLML-BW3P3-BUCMWZ":{"refid":null,"userref":7,"status":"open","opentm":1688666559.8974,"starttm":0,"expiretm":0,
+        "descr":{"pair":"XBTUSD","type":"sell","ordertype":"limit","price":"30010.0","price2":"0","leverage":"none","order":"sell 1.25 XBTUSD @ limit 30010.0","close":""},
+        "vol":"1.25000000","vol_exec":"0.37500000","cost":"11253.7","fee":"0.00000","price":"30010.0","stopprice":"0.00000","limitprice":"0.00000","misc":"","oflags":"fciq"}}}}"#,
+    );
+
+    let req = OpenOrdersRequest {
+        userref: Some(7),
+        ..OpenOrdersRequest::default()
+    };
+    let resp = transport.client().request(req).await.unwrap();
+    assert_eq!(params(&transport.body()), "userref=7");
+
+    let order = &resp.open["OQCLML-BW3P3-BUCMWZ"];
+    assert_eq!(order.status, OrderStatus::Open);
+    assert_eq!(order.close_time, None);
+    assert!(order.misc.is_empty());
+    assert!(order.trades.is_empty());
+}
+
+#[tokio::test]
+async fn test_unknown_order_type() {
+    let order = ORDER.replace(r#""ordertype":"stop-loss-limit""#, r#""ordertype":"pegged-limit""#);
+    let transport = FakeTransport::new(&format!(r#"{{"error":[],"result":{{"OBCMZD-JIEE7-77TH3F":{}}}}}"#, order));
+    let resp = transport.client().request(QueryOrdersRequest::new(&["OBCMZD-JIEE7-77TH3F"]).unwrap()).await.unwrap();
+    assert_eq!(resp["OBCMZD-JIEE7-77TH3F"].descr.order_type, OrderType::Other);
+
+    let trade = OWN_TRADE.replace(r#""ordertype":"limit""#, r#""ordertype":"pegged-limit""#);
+    let transport = FakeTransport::new(&page("trades", &[("T1", &trade)], Some(1)));
+    let resp = transport.client().request(TradesHistoryRequest::default()).await.unwrap();
+    assert_eq!(resp.trades["T1"].order_type, OrderType::Other);
+}
+
+#[tokio::test]
+async fn test_closed_orders_stream() {
+    let transport = FakeTransport::pages(vec![page("closed", &[("O1", ORDER), ("O2", ORDER)], Some(3)), page("closed", &[("O3", ORDER)], Some(3))]);
+
+    let req = ClosedOrdersRequest {
+        start: Some(1688000000),
+        close_time: Some(CloseTime::Close),
+        ..ClosedOrdersRequest::default()
+    };
+    let orders: Vec<_> = transport.client().stream(req).try_collect().await.unwrap();
+
+    let mut txids: Vec<_> = orders.iter().map(|(txid, _)| txid.as_str()).collect();
+    txids.sort();
+    assert_eq!(txids, vec!["O1", "O2", "O3"]);
+
+    let bodies: Vec<_> = transport.bodies().iter().map(|b| params(b).to_string()).collect();
+    assert_eq!(bodies, vec!["closetime=close&start=1688000000", "closetime=close&ofs=2&start=1688000000"]);
+}
+
+const OWN_TRADE: &str = r#"{"ordertxid":"OQCLML-BW3P3-BUCMWZ","postxid":"TKH2SE-M7IF5-CFI7LT","pair":"XXBTZUSD","time":1688667796.8802,"type":"buy","ordertype":"limit",
+    "price":"30010.00000","cost":"600.20000","fee":"0.00000","vol":"0.02000000","margin":"0.00000","leverage":"0","misc":"","trade_id":40274859,"maker":true}"#;
+
+#[tokio::test]
+async fn test_trades_history_stream() {
+    let transport = FakeTransport::pages(vec![page("trades", &[("T1", OWN_TRADE)], Some(2)), page("trades", &[("T2", OWN_TRADE)], Some(2))]);
+
+    let req = TradesHistoryRequest {
+        filter: Some(TradeFilter::NoPosition),
+        end: Some(1688700000),
+        ..TradesHistoryRequest::default()
+    };
+    let trades: Vec<_> = transport.client().stream(req).try_collect().await.unwrap();
+
+    assert_eq!(trades.iter().map(|(txid, _)| txid.as_str()).collect::<Vec<_>>(), vec!["T1", "T2"]);
+    let bodies: Vec<_> = transport.bodies().iter().map(|b| params(b).to_string()).collect();
+    assert_eq!(bodies, vec!["end=1688700000&type=no+position", "end=1688700000&ofs=1&type=no+position"]);
+
+    let trade = &trades[0].1;
+    assert_eq!(trade.order_txid, "OQCLML-BW3P3-BUCMWZ");
+    assert_eq!(trade.pos_txid.as_deref(), Some("TKH2SE-M7IF5-CFI7LT"));
+    assert_eq!(trade.side, Side::Buy);
+    assert_eq!(trade.order_type, OrderType::Limit);
+    assert_eq!(trade.cost, 600.2);
+    assert_eq!(trade.volume, 0.02);
+    assert_eq!(trade.margin, 0.);
+    assert_eq!(trade.trade_id, Some(40274859));
+    assert_eq!(trade.maker, Some(true));
+}
+
+#[tokio::test]
+async fn test_query_trades() {
+    let transport = FakeTransport::new(&format!(r#"{{"error":[],"result":{{"THVRQM-33VKH-UCI7BS":{}}}}}"#, OWN_TRADE));
+
+    let resp = transport.client().request(QueryTradesRequest::new(&["THVRQM-33VKH-UCI7BS"]).unwrap()).await.unwrap();
+    assert_eq!(params(&transport.body()), "txid=THVRQM-33VKH-UCI7BS");
+    assert_eq!(resp["THVRQM-33VKH-UCI7BS"].price, 30010.);
+}
+
+#[test]
+fn test_query_trades_and_ledgers_id_limits() {
+    let ids: Vec<_> = (0..=QueryTradesRequest::MAX_TXIDS).map(|i| format!("T{}", i)).collect();
+
+    assert!(QueryTradesRequest::new(&ids[..QueryTradesRequest::MAX_TXIDS]).is_ok());
+    assert!(matches!(QueryTradesRequest::new(&ids), Err(KrakenError::InvalidIdList(_))));
+    assert!(QueryLedgersRequest::new(&ids[..QueryLedgersRequest::MAX_IDS]).is_ok());
+    assert!(matches!(
+        QueryLedgersRequest::new(&ids[..QueryLedgersRequest::MAX_IDS + 1]),
+        Err(KrakenError::InvalidIdList(_))
+    ));
+    assert!(matches!(QueryLedgersRequest::new::<&str>(&[]), Err(KrakenError::InvalidIdList(_))));
+}
+
+const LEDGER_ENTRY: &str = r#"{"aclass":"currency","amount":"-0.2500","asset":"XETH","balance":"1.7500","fee":"0.0020","refid":"TJKLXX-PGMUI-4NTLXU","time":1688464484.1787,"type":"transfer","subtype":"spottostaking"}"#;
+
+#[tokio::test]
+async fn test_ledgers_stream_without_count() {
+    let transport = FakeTransport::pages(vec![
+        page("ledger", &[("L1", LEDGER_ENTRY), ("L2", LEDGER_ENTRY)], None),
+        page("ledger", &[("L3", LEDGER_ENTRY)], None),
+        page("ledger", &[], None),
+    ]);
+
+    let req = LedgersRequest {
+        asset: Some("ETH".to_string()),
+        ledger_type: Some(LedgerType::Transfer),
+        without_count: Some(true),
+        ..LedgersRequest::default()
+    };
+    let entries: Vec<_> = transport.client().stream(req).try_collect().await.unwrap();
+
+    // Without a count the stream stops at the first empty page
+    assert_eq!(entries.len(), 3);
+    assert_eq!(transport.count(), 3);
+    assert_eq!(params(&transport.body()), "asset=ETH&ofs=3&type=transfer&without_count=true");
+
+    let entry = &entries[0].1;
+    assert_eq!(entry.ledger_type, LedgerType::Transfer);
+    assert_eq!(entry.subtype, "spottostaking");
+    assert_eq!(entry.asset, currency("ETH"));
+    assert_eq!(entry.amount, -0.25);
+    assert_eq!(entry.fee, 0.002);
+    assert_eq!(entry.balance, 1.75);
+}
+
+#[tokio::test]
+async fn test_query_ledgers() {
+    let transport = FakeTransport::new(
+        r#"{"error":[],"result":{"L4UESK-KG3EQ-UFO4T5":{"aclass":"currency","amount":"12.0","asset":"DOT.S","balance":"12.0","fee":"0","refid":"RUSB7W6-ESIXUX-K6PVTM","time":1688540000,"type":"airdrop","subtype":""}}}"#,
+    );
+
+    let resp = transport
+        .client()
+        .request(QueryLedgersRequest::new(&["L4UESK-KG3EQ-UFO4T5", "LGT7MT-D5TIX-QBOH5T"]).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(params(&transport.body()), "id=L4UESK-KG3EQ-UFO4T5%2CLGT7MT-D5TIX-QBOH5T");
+
+    let entry = &resp["L4UESK-KG3EQ-UFO4T5"];
+    assert_eq!(entry.ledger_type, LedgerType::Other);
+    assert_eq!(entry.asset.base(), currency("DOT"));
+}